@@ -0,0 +1,267 @@
+use num_traits::Float;
+
+use crate::matrix::{Matrix3x3, Matrix4x4};
+use crate::vector::Vector3;
+
+/// Axis sequence of an Euler angle triple. The first six are Tait-Bryan orders (three
+/// distinct axes), the last six are proper Euler orders (first and last axis repeat).
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EulerOrder {
+    XYZ,
+    XZY,
+    YXZ,
+    YZX,
+    ZXY,
+    ZYX,
+    XYX,
+    XZX,
+    YXY,
+    YZY,
+    ZXZ,
+    ZYZ,
+}
+
+/// Whether each rotation is about the axes of the already-rotated frame (intrinsic) or
+/// about the fixed world axes (extrinsic).
+///
+/// Intrinsic `XYZ` with angles `(a, b, c)` is `Rx(a) * Ry(b) * Rz(c)`; extrinsic `XYZ` is
+/// `Rz(c) * Ry(b) * Rx(a)`, the same as intrinsic `ZYX` with the angles reversed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EulerFrame {
+    #[default]
+    Intrinsic,
+    Extrinsic,
+}
+
+impl EulerOrder {
+    /// Axis indices (0 = X, 1 = Y, 2 = Z) in the order the angles apply
+    pub const fn axes(self) -> [usize; 3] {
+        match self {
+            EulerOrder::XYZ => [0, 1, 2],
+            EulerOrder::XZY => [0, 2, 1],
+            EulerOrder::YXZ => [1, 0, 2],
+            EulerOrder::YZX => [1, 2, 0],
+            EulerOrder::ZXY => [2, 0, 1],
+            EulerOrder::ZYX => [2, 1, 0],
+            EulerOrder::XYX => [0, 1, 0],
+            EulerOrder::XZX => [0, 2, 0],
+            EulerOrder::YXY => [1, 0, 1],
+            EulerOrder::YZY => [1, 2, 1],
+            EulerOrder::ZXZ => [2, 0, 2],
+            EulerOrder::ZYZ => [2, 1, 2],
+        }
+    }
+
+    /// True for proper Euler orders such as `ZXZ`
+    pub const fn is_proper_euler(self) -> bool {
+        let [first, _, last] = self.axes();
+        first == last
+    }
+}
+
+// Rotation by `angle` about coordinate axis `axis`
+fn axis_rotation<T: Float>(axis: usize, angle: T) -> Matrix3x3<T> {
+    let (sin, cos) = angle.sin_cos();
+    let mut m = Matrix3x3::identity();
+    let (p, q) = ((axis + 1) % 3, (axis + 2) % 3);
+    m[p][p] = cos;
+    m[p][q] = -sin;
+    m[q][p] = sin;
+    m[q][q] = cos;
+    m
+}
+
+// Angle of a matrix that rotates only about coordinate axis `axis`
+fn axis_angle<T: Float>(axis: usize, m: &Matrix3x3<T>) -> T {
+    let (p, q) = ((axis + 1) % 3, (axis + 2) % 3);
+    m[q][p].atan2(m[p][p])
+}
+
+// Intrinsic angles for axes [i, j, k] (k == i for proper Euler orders)
+fn intrinsic_angles<T: Float>(m: &Matrix3x3<T>, [i, j, k]: [usize; 3]) -> [T; 3] {
+    // Below this the first and last axes are treated as aligned. The square root balances
+    // the error of dropping the last angle against the error of the general formulas.
+    let lock_tolerance = T::epsilon().sqrt();
+    let proper = i == k;
+    let k = if proper { 3 - i - j } else { k };
+    // +1 when (i, j, k) is a cyclic permutation of (X, Y, Z)
+    let parity = if (i + 1) % 3 == j {
+        T::one()
+    } else {
+        -T::one()
+    };
+
+    let (middle, locked) = if proper {
+        let sin_b = m[i][j].hypot(m[i][k]);
+        (sin_b.atan2(m[i][i]), sin_b <= lock_tolerance)
+    } else {
+        let cos_b = m[i][i].hypot(m[i][j]);
+        ((parity * m[i][k]).atan2(cos_b), cos_b <= lock_tolerance)
+    };
+
+    if locked {
+        // Only the sum of the outer angles is defined: put it all in the first one
+        let first = *m * axis_rotation(j, -middle);
+        return [axis_angle(i, &first), middle, T::zero()];
+    }
+
+    if proper {
+        [
+            m[j][i].atan2(-parity * m[k][i]),
+            middle,
+            m[i][j].atan2(parity * m[i][k]),
+        ]
+    } else {
+        [
+            (-parity * m[j][k]).atan2(m[k][k]),
+            middle,
+            (-parity * m[i][j]).atan2(m[i][i]),
+        ]
+    }
+}
+
+impl<T: Float> Matrix3x3<T> {
+    /// Rotation from Euler angles, given in the order the axes appear in `order`
+    pub fn from_euler(angles: Vector3<T>, order: EulerOrder, frame: EulerFrame) -> Self {
+        let [a, b, c] = order.axes();
+        let (ra, rb, rc) = (
+            axis_rotation(a, angles[0]),
+            axis_rotation(b, angles[1]),
+            axis_rotation(c, angles[2]),
+        );
+        match frame {
+            EulerFrame::Intrinsic => ra * rb * rc,
+            EulerFrame::Extrinsic => rc * rb * ra,
+        }
+    }
+
+    /// Euler angles of a rotation matrix, in the order the axes appear in `order`.
+    ///
+    /// The middle angle is in [-pi/2, pi/2] for Tait-Bryan orders and [0, pi] for proper
+    /// Euler orders. At gimbal lock the last angle is zero and the first carries the rotation.
+    pub fn to_euler(&self, order: EulerOrder, frame: EulerFrame) -> Vector3<T> {
+        let [a, b, c] = order.axes();
+        match frame {
+            EulerFrame::Intrinsic => Vector3::from_array(intrinsic_angles(self, [a, b, c])),
+            EulerFrame::Extrinsic => {
+                let [z, y, x] = intrinsic_angles(self, [c, b, a]);
+                Vector3::new(x, y, z)
+            }
+        }
+    }
+}
+
+impl<T: Float> Matrix4x4<T> {
+    /// Homogeneous rotation from Euler angles, see `Matrix3x3::from_euler`
+    pub fn from_euler(angles: Vector3<T>, order: EulerOrder, frame: EulerFrame) -> Self {
+        let r = Matrix3x3::from_euler(angles, order, frame);
+        let mut m = Matrix4x4::identity();
+        for i in 0..3 {
+            m[i][..3].copy_from_slice(&r[i]);
+        }
+        m
+    }
+
+    /// Euler angles of the upper-left 3x3 rotation block, see `Matrix3x3::to_euler`
+    pub fn to_euler(&self, order: EulerOrder, frame: EulerFrame) -> Vector3<T> {
+        Matrix3x3::from_2d_array([
+            [self[0][0], self[0][1], self[0][2]],
+            [self[1][0], self[1][1], self[1][2]],
+            [self[2][0], self[2][1], self[2][2]],
+        ])
+        .to_euler(order, frame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EulerFrame, EulerOrder};
+    use crate::test_util::assert_matrix_eq;
+    use crate::vector::Vector3f64;
+    use crate::{Matrix3x3f64, Matrix4x4f64, Transform4x4};
+
+    const ORDERS: [EulerOrder; 12] = [
+        EulerOrder::XYZ,
+        EulerOrder::XZY,
+        EulerOrder::YXZ,
+        EulerOrder::YZX,
+        EulerOrder::ZXY,
+        EulerOrder::ZYX,
+        EulerOrder::XYX,
+        EulerOrder::XZX,
+        EulerOrder::YXY,
+        EulerOrder::YZY,
+        EulerOrder::ZXZ,
+        EulerOrder::ZYZ,
+    ];
+    const FRAMES: [EulerFrame; 2] = [EulerFrame::Intrinsic, EulerFrame::Extrinsic];
+
+    #[test]
+    fn test_euler_round_trip() {
+        for order in ORDERS {
+            // Middle angle kept inside the canonical range so the angles come back unchanged
+            let middle = if order.is_proper_euler() { 1.1 } else { -0.7 };
+            let angles = Vector3f64::new(0.4, middle, -2.3);
+            for frame in FRAMES {
+                let m = Matrix3x3f64::from_euler(angles, order, frame);
+                let back = m.to_euler(order, frame);
+                for i in 0..3 {
+                    assert!(
+                        (back[i] - angles[i]).abs() < 1e-12,
+                        "{:?} {:?}",
+                        order,
+                        frame
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_gimbal_lock() {
+        let half_pi = core::f64::consts::FRAC_PI_2;
+        for order in ORDERS {
+            let middle = if order.is_proper_euler() {
+                0.0
+            } else {
+                half_pi
+            };
+            let angles = Vector3f64::new(0.3, middle, 0.5);
+            for frame in FRAMES {
+                let m = Matrix3x3f64::from_euler(angles, order, frame);
+                let back = m.to_euler(order, frame);
+                assert!(back[0].is_finite() && back[1].is_finite() && back[2].is_finite());
+                assert_matrix_eq(&Matrix3x3f64::from_euler(back, order, frame), &m, 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_euler_matches_axis_rotations() {
+        let (a, b, c) = (0.2, -0.4, 1.3);
+        let angles = Vector3f64::new(a, b, c);
+        let expected =
+            Matrix4x4f64::rotation_z(c) * Matrix4x4f64::rotation_y(b) * Matrix4x4f64::rotation_x(a);
+
+        let intrinsic = Matrix4x4f64::from_euler(
+            Vector3f64::new(c, b, a),
+            EulerOrder::ZYX,
+            EulerFrame::Intrinsic,
+        );
+        let extrinsic = Matrix4x4f64::from_euler(angles, EulerOrder::XYZ, EulerFrame::Extrinsic);
+        for ((x, y), z) in intrinsic
+            .as_slice()
+            .iter()
+            .zip(extrinsic.as_slice())
+            .zip(expected.as_slice())
+        {
+            assert!((x - z).abs() < 1e-12 && (y - z).abs() < 1e-12);
+        }
+
+        let back = expected.to_euler(EulerOrder::XYZ, EulerFrame::Extrinsic);
+        assert!((back[0] - a).abs() < 1e-12);
+        assert!((back[1] - b).abs() < 1e-12);
+        assert!((back[2] - c).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,294 @@
+use core::array;
+
+use num_traits::Float;
+
+use crate::geometry::dot;
+use crate::matrix::{Matrix3x3, Matrix4x4};
+use crate::vector::Vector;
+
+/// Axis-aligned bounding box spanning `min` to `max` inclusive.
+///
+/// A box with any `min` component greater than the matching `max` component is empty.
+/// `Aabb::empty()` is the identity for `union` and `expand`, so boxes can be grown one point
+/// at a time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb<T, const D: usize> {
+    pub min: Vector<T, D>,
+    pub max: Vector<T, D>,
+}
+
+pub type Aabb2<T> = Aabb<T, 2>;
+pub type Aabb3<T> = Aabb<T, 3>;
+
+pub type Aabb2f32 = Aabb2<f32>;
+pub type Aabb2f64 = Aabb2<f64>;
+pub type Aabb3f32 = Aabb3<f32>;
+pub type Aabb3f64 = Aabb3<f64>;
+
+impl<T: Float, const D: usize> Aabb<T, D> {
+    pub fn new(min: Vector<T, D>, max: Vector<T, D>) -> Self {
+        Aabb { min, max }
+    }
+
+    /// Box containing nothing
+    pub fn empty() -> Self {
+        Aabb {
+            min: Vector::from_array([T::infinity(); D]),
+            max: Vector::from_array([T::neg_infinity(); D]),
+        }
+    }
+
+    /// Smallest box containing all points, `None` when there are none
+    pub fn from_points(points: &[Vector<T, D>]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        Some(
+            points
+                .iter()
+                .fold(Aabb::empty(), |aabb, &point| aabb.expand(point)),
+        )
+    }
+
+    /// Box centered on `center` reaching `half_extents` along each axis
+    pub fn from_center_half_extents(center: Vector<T, D>, half_extents: Vector<T, D>) -> Self {
+        Aabb {
+            min: center - half_extents,
+            max: center + half_extents,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        (0..D).any(|i| self.min[i] > self.max[i])
+    }
+
+    pub fn center(&self) -> Vector<T, D> {
+        let half = T::one() / (T::one() + T::one());
+        (self.min + self.max) * half
+    }
+
+    pub fn size(&self) -> Vector<T, D> {
+        self.max - self.min
+    }
+
+    /// Half the size along each axis
+    pub fn extents(&self) -> Vector<T, D> {
+        let half = T::one() / (T::one() + T::one());
+        self.size() * half
+    }
+
+    /// Area in 2D, volume in 3D
+    pub fn volume(&self) -> T {
+        let size = self.size();
+        (0..D).fold(T::one(), |product, i| product * size[i])
+    }
+
+    /// Boundary measure: surface area in 3D, perimeter in 2D
+    pub fn surface_area(&self) -> T {
+        let size = self.size();
+        let faces = (0..D).fold(T::zero(), |sum, skip| {
+            sum + (0..D)
+                .filter(|&i| i != skip)
+                .fold(T::one(), |product, i| product * size[i])
+        });
+        faces * (T::one() + T::one())
+    }
+
+    pub fn contains_point(&self, point: Vector<T, D>) -> bool {
+        (0..D).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    /// True when `other` lies entirely inside this box
+    pub fn contains(&self, other: &Self) -> bool {
+        (0..D).all(|i| self.min[i] <= other.min[i] && other.max[i] <= self.max[i])
+    }
+
+    /// True when the boxes overlap, including touching faces
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..D).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Aabb {
+            min: Vector::from_array(array::from_fn(|i| self.min[i].min(other.min[i]))),
+            max: Vector::from_array(array::from_fn(|i| self.max[i].max(other.max[i]))),
+        }
+    }
+
+    /// Overlapping region, `None` when the boxes are disjoint
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let overlap = Aabb {
+            min: Vector::from_array(array::from_fn(|i| self.min[i].max(other.min[i]))),
+            max: Vector::from_array(array::from_fn(|i| self.max[i].min(other.max[i]))),
+        };
+        if overlap.is_empty() {
+            None
+        } else {
+            Some(overlap)
+        }
+    }
+
+    /// Grow to include `point`
+    pub fn expand(&self, point: Vector<T, D>) -> Self {
+        Aabb {
+            min: Vector::from_array(array::from_fn(|i| self.min[i].min(point[i]))),
+            max: Vector::from_array(array::from_fn(|i| self.max[i].max(point[i]))),
+        }
+    }
+
+    /// Grow by `margin` on every side
+    pub fn inflate(&self, margin: T) -> Self {
+        Aabb {
+            min: Vector::from_array(array::from_fn(|i| self.min[i] - margin)),
+            max: Vector::from_array(array::from_fn(|i| self.max[i] + margin)),
+        }
+    }
+
+    /// Point inside the box closest to `point`
+    pub fn closest_point(&self, point: Vector<T, D>) -> Vector<T, D> {
+        Vector::from_array(array::from_fn(|i| {
+            point[i].max(self.min[i]).min(self.max[i])
+        }))
+    }
+
+    /// Squared distance from `point` to the box, zero when the point is inside
+    pub fn distance_squared(&self, point: Vector<T, D>) -> T {
+        let offset = point - self.closest_point(point);
+        dot(&offset, &offset)
+    }
+
+    // Box around this one after an affine transform with the given linear part and
+    // translation (Arvo's method)
+    fn transformed(&self, linear: impl Fn(usize, usize) -> T, translation: [T; D]) -> Self {
+        if self.is_empty() {
+            return *self;
+        }
+        let mut min = translation;
+        let mut max = translation;
+        for i in 0..D {
+            for j in 0..D {
+                let a = linear(i, j) * self.min[j];
+                let b = linear(i, j) * self.max[j];
+                min[i] = min[i] + a.min(b);
+                max[i] = max[i] + a.max(b);
+            }
+        }
+        Aabb {
+            min: Vector::from_array(min),
+            max: Vector::from_array(max),
+        }
+    }
+}
+
+impl<T: Float> Aabb2<T> {
+    /// Re-fitted box around this one after an affine 2D transform
+    pub fn transform(&self, m: &Matrix3x3<T>) -> Self {
+        self.transformed(|i, j| m[i][j], [m[0][2], m[1][2]])
+    }
+}
+
+impl<T: Float> Aabb3<T> {
+    /// Re-fitted box around this one after an affine transform. The result can be larger
+    /// than the tightest box around the transformed contents.
+    pub fn transform(&self, m: &Matrix4x4<T>) -> Self {
+        self.transformed(|i, j| m[i][j], [m[0][3], m[1][3], m[2][3]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Aabb2f64, Aabb3f64};
+    use crate::vector::{Vector2f64, Vector3f64};
+    use crate::{Matrix4x4f64, Transform4x4};
+
+    fn unit_cube() -> Aabb3f64 {
+        Aabb3f64::new(
+            Vector3f64::new(-1.0, -1.0, -1.0),
+            Vector3f64::new(1.0, 1.0, 1.0),
+        )
+    }
+
+    #[test]
+    fn test_aabb_from_points() {
+        let points = [
+            Vector2f64::new(1.0, 5.0),
+            Vector2f64::new(-2.0, 3.0),
+            Vector2f64::new(4.0, -1.0),
+        ];
+        let aabb = Aabb2f64::from_points(&points).unwrap();
+        assert_eq!(aabb.min, Vector2f64::new(-2.0, -1.0));
+        assert_eq!(aabb.max, Vector2f64::new(4.0, 5.0));
+        assert_eq!(aabb.center(), Vector2f64::new(1.0, 2.0));
+        assert_eq!(aabb.extents(), Vector2f64::new(3.0, 3.0));
+        assert_eq!(aabb.volume(), 36.0);
+        assert_eq!(aabb.surface_area(), 24.0);
+        assert!(points.iter().all(|&p| aabb.contains_point(p)));
+
+        assert!(Aabb2f64::from_points(&[]).is_none());
+        assert!(Aabb2f64::empty().is_empty());
+        assert_eq!(Aabb2f64::empty().union(&aabb), aabb);
+    }
+
+    #[test]
+    fn test_aabb_set_operations() {
+        let a = unit_cube();
+        let b = Aabb3f64::new(
+            Vector3f64::new(0.0, 0.5, -3.0),
+            Vector3f64::new(2.0, 3.0, 0.0),
+        );
+        assert!(a.intersects(&b));
+        assert_eq!(
+            a.intersection(&b).unwrap(),
+            Aabb3f64::new(
+                Vector3f64::new(0.0, 0.5, -1.0),
+                Vector3f64::new(1.0, 1.0, 0.0)
+            )
+        );
+        assert_eq!(
+            a.union(&b),
+            Aabb3f64::new(
+                Vector3f64::new(-1.0, -1.0, -3.0),
+                Vector3f64::new(2.0, 3.0, 1.0)
+            )
+        );
+        assert!(a.union(&b).contains(&a) && !a.contains(&b));
+
+        let far = Aabb3f64::from_center_half_extents(
+            Vector3f64::new(5.0, 0.0, 0.0),
+            Vector3f64::new(1.0, 1.0, 1.0),
+        );
+        assert!(!a.intersects(&far));
+        assert!(a.intersection(&far).is_none());
+        assert!(a.inflate(3.0).intersects(&far));
+        assert_eq!(a.surface_area(), 24.0);
+        assert_eq!(
+            a.expand(Vector3f64::new(0.0, 4.0, 0.0)).max,
+            Vector3f64::new(1.0, 4.0, 1.0)
+        );
+        assert_eq!(
+            a.closest_point(Vector3f64::new(3.0, 0.5, -2.0)),
+            Vector3f64::new(1.0, 0.5, -1.0)
+        );
+        assert_eq!(a.distance_squared(Vector3f64::new(3.0, 0.5, -2.0)), 5.0);
+        assert_eq!(a.distance_squared(Vector3f64::new(0.5, 0.5, 0.5)), 0.0);
+    }
+
+    #[test]
+    fn test_aabb_transform() {
+        let m = Matrix4x4f64::translation(Vector3f64::new(10.0, 0.0, 0.0))
+            * Matrix4x4f64::rotation_z(core::f64::consts::FRAC_PI_4)
+            * Matrix4x4f64::scale(Vector3f64::new(2.0, 1.0, 1.0));
+        let moved = unit_cube().transform(&m);
+
+        // Every transformed corner is inside and the box touches the extreme ones
+        let half_diagonal = 3.0 / 2.0f64.sqrt();
+        assert!((moved.min[0] - (10.0 - half_diagonal)).abs() < 1e-12);
+        assert!((moved.max[1] - half_diagonal).abs() < 1e-12);
+        assert!((moved.max[2] - 1.0).abs() < 1e-12);
+        for corner in 0..8 {
+            let pick = |bit: usize| if corner & bit == 0 { -1.0 } else { 1.0 };
+            let p = m.transform_point(Vector3f64::new(pick(1), pick(2), pick(4)));
+            assert!(moved.inflate(1e-12).contains_point(p));
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use num_traits::Float;
+
+use crate::geometry::Segment;
+use crate::vector::Vector;
+
+/// Points within `radius` of the segment from `a` to `b`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Capsule<T, const D: usize> {
+    pub a: Vector<T, D>,
+    pub b: Vector<T, D>,
+    pub radius: T,
+}
+
+pub type Capsule2<T> = Capsule<T, 2>;
+pub type Capsule3<T> = Capsule<T, 3>;
+
+impl<T: Float, const D: usize> Capsule<T, D> {
+    pub fn new(a: Vector<T, D>, b: Vector<T, D>, radius: T) -> Self {
+        Capsule { a, b, radius }
+    }
+
+    /// The capsule's core segment
+    pub fn segment(&self) -> Segment<T, D> {
+        Segment::new(self.a, self.b)
+    }
+}
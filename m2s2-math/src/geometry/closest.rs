@@ -0,0 +1,276 @@
+use core::array;
+
+use num_traits::Float;
+
+use crate::geometry::{Obb, Obb2, Segment, Triangle, dot};
+use crate::vector::vector_ops::{Vector2Ops, Vector3Ops};
+use crate::vector::{Vector, Vector2, Vector3};
+
+/// Closest point on a shape to a query point
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointQuery<T, const D: usize> {
+    pub point: Vector<T, D>,
+    pub distance_squared: T,
+}
+
+/// Closest pair of points between two shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClosestPoints<T, const D: usize> {
+    pub first: Vector<T, D>,
+    pub second: Vector<T, D>,
+    pub distance_squared: T,
+}
+
+fn point_query<T: Float, const D: usize>(
+    point: Vector<T, D>,
+    query: Vector<T, D>,
+) -> PointQuery<T, D> {
+    let offset = query - point;
+    PointQuery {
+        point,
+        distance_squared: dot(&offset, &offset),
+    }
+}
+
+impl<T: Float, const D: usize> Segment<T, D> {
+    /// Closest point on the segment to `point`
+    pub fn closest_point(&self, point: Vector<T, D>) -> PointQuery<T, D> {
+        let direction = self.b - self.a;
+        let length_squared = dot(&direction, &direction);
+        let t = if length_squared > T::zero() {
+            (dot(&(point - self.a), &direction) / length_squared)
+                .max(T::zero())
+                .min(T::one())
+        } else {
+            T::zero()
+        };
+        point_query(self.at(t), point)
+    }
+
+    /// Closest points between two segments, `first` on this one and `second` on `other`.
+    /// For parallel segments one of the equally close pairs is returned.
+    pub fn closest_points(&self, other: &Self) -> ClosestPoints<T, D> {
+        // Ericson, Real-Time Collision Detection 5.1.9
+        let clamp = |x: T| x.max(T::zero()).min(T::one());
+        let (d1, d2, r) = (self.b - self.a, other.b - other.a, self.a - other.a);
+        let (a, e, f) = (dot(&d1, &d1), dot(&d2, &d2), dot(&d2, &r));
+
+        let (s, t) = if a <= T::epsilon() && e <= T::epsilon() {
+            (T::zero(), T::zero())
+        } else if a <= T::epsilon() {
+            (T::zero(), clamp(f / e))
+        } else {
+            let c = dot(&d1, &r);
+            if e <= T::epsilon() {
+                (clamp(-c / a), T::zero())
+            } else {
+                let b = dot(&d1, &d2);
+                let denominator = a * e - b * b;
+                // Parallel segments: any s works, start from `a`
+                let s = if denominator > T::zero() {
+                    clamp((b * f - c * e) / denominator)
+                } else {
+                    T::zero()
+                };
+                let t = (b * s + f) / e;
+                if t < T::zero() {
+                    (clamp(-c / a), T::zero())
+                } else if t > T::one() {
+                    (clamp((b - c) / a), T::one())
+                } else {
+                    (s, t)
+                }
+            }
+        };
+
+        let (first, second) = (self.at(s), other.at(t));
+        let offset = first - second;
+        ClosestPoints {
+            first,
+            second,
+            distance_squared: dot(&offset, &offset),
+        }
+    }
+}
+
+// Barycentric weights of the point of triangle abc closest to p (Ericson 5.1.5: find the
+// Voronoi region of the triangle containing p)
+pub(crate) fn triangle_closest_weights<T: Float>(
+    a: Vector3<T>,
+    b: Vector3<T>,
+    c: Vector3<T>,
+    p: Vector3<T>,
+) -> [T; 3] {
+    let (zero, one) = (T::zero(), T::one());
+    let (ab, ac) = (b - a, c - a);
+
+    let ap = p - a;
+    let (d1, d2) = (ab.dot(&ap), ac.dot(&ap));
+    if d1 <= zero && d2 <= zero {
+        return [one, zero, zero];
+    }
+
+    let bp = p - b;
+    let (d3, d4) = (ab.dot(&bp), ac.dot(&bp));
+    if d3 >= zero && d4 <= d3 {
+        return [zero, one, zero];
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= zero && d1 >= zero && d3 <= zero {
+        let t = d1 / (d1 - d3);
+        return [one - t, t, zero];
+    }
+
+    let cp = p - c;
+    let (d5, d6) = (ab.dot(&cp), ac.dot(&cp));
+    if d6 >= zero && d5 <= d6 {
+        return [zero, zero, one];
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= zero && d2 >= zero && d6 <= zero {
+        let t = d2 / (d2 - d6);
+        return [one - t, zero, t];
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= zero && d4 - d3 >= zero && d5 - d6 >= zero {
+        let t = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+        return [zero, one - t, t];
+    }
+
+    let denominator = one / (va + vb + vc);
+    let (v, w) = (vb * denominator, vc * denominator);
+    [one - v - w, v, w]
+}
+
+impl<T: Float> Triangle<T> {
+    /// Closest point on the triangle, including its interior, to `point`
+    pub fn closest_point(&self, point: Vector3<T>) -> PointQuery<T, 3> {
+        let [u, v, w] = triangle_closest_weights(self.a, self.b, self.c, point);
+        point_query(self.a * u + self.b * v + self.c * w, point)
+    }
+}
+
+impl<T: Float> Obb<T> {
+    /// Closest point inside the box to `point`
+    pub fn closest_point(&self, point: Vector3<T>) -> PointQuery<T, 3> {
+        let local = self.to_local(point);
+        let h = self.half_extents;
+        let clamped = Vector3::from_array(array::from_fn(|i| local[i].max(-h[i]).min(h[i])));
+        point_query(self.center + self.rotation.rotate_vector(clamped), point)
+    }
+}
+
+impl<T: Float> Obb2<T> {
+    /// Closest point inside the box to `point`
+    pub fn closest_point(&self, point: Vector2<T>) -> PointQuery<T, 2> {
+        let offset = point - self.center;
+        let closest = self
+            .axes()
+            .iter()
+            .enumerate()
+            .fold(self.center, |sum, (i, axis)| {
+                let h = self.half_extents[i];
+                sum + *axis * offset.dot(axis).max(-h).min(h)
+            });
+        point_query(closest, point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::{Obb, Obb2, Segment, Triangle};
+    use crate::quaternian::Quaternion;
+    use crate::test_util::{assert_vec_near, v3};
+    use crate::vector::Vector2f64;
+    use crate::vector::vector_ops::Vector3Ops;
+
+    #[test]
+    fn test_closest_point_on_segment() {
+        let segment = Segment::new(v3(0.0, 0.0, 0.0), v3(4.0, 0.0, 0.0));
+        let inner = segment.closest_point(v3(1.0, 2.0, 0.0));
+        assert_vec_near(inner.point, v3(1.0, 0.0, 0.0), 1e-12);
+        assert_eq!(inner.distance_squared, 4.0);
+        assert_vec_near(
+            segment.closest_point(v3(-3.0, 1.0, 0.0)).point,
+            v3(0.0, 0.0, 0.0),
+            1e-12,
+        );
+        assert_vec_near(
+            segment.closest_point(v3(9.0, 0.0, 1.0)).point,
+            v3(4.0, 0.0, 0.0),
+            1e-12,
+        );
+
+        let degenerate = Segment::new(v3(1.0, 1.0, 1.0), v3(1.0, 1.0, 1.0));
+        assert_eq!(
+            degenerate.closest_point(v3(1.0, 1.0, 3.0)).distance_squared,
+            4.0
+        );
+    }
+
+    #[test]
+    fn test_closest_points_between_segments() {
+        let a = Segment::new(v3(0.0, 0.0, 0.0), v3(4.0, 0.0, 0.0));
+
+        // Skew segments crossing above each other
+        let b = Segment::new(v3(1.0, -2.0, 3.0), v3(1.0, 2.0, 3.0));
+        let closest = a.closest_points(&b);
+        assert_vec_near(closest.first, v3(1.0, 0.0, 0.0), 1e-12);
+        assert_vec_near(closest.second, v3(1.0, 0.0, 3.0), 1e-12);
+        assert_eq!(closest.distance_squared, 9.0);
+
+        // Closest at the endpoints
+        let c = Segment::new(v3(6.0, 1.0, 0.0), v3(9.0, 5.0, 0.0));
+        let closest = a.closest_points(&c);
+        assert_vec_near(closest.first, v3(4.0, 0.0, 0.0), 1e-12);
+        assert_vec_near(closest.second, v3(6.0, 1.0, 0.0), 1e-12);
+
+        // Parallel, overlapping in x
+        let d = Segment::new(v3(2.0, 1.0, 0.0), v3(7.0, 1.0, 0.0));
+        assert!((a.closest_points(&d).distance_squared - 1.0).abs() < 1e-12);
+
+        let flat = Segment::new(Vector2f64::new(0.0, 0.0), Vector2f64::new(2.0, 2.0));
+        let across = Segment::new(Vector2f64::new(0.0, 2.0), Vector2f64::new(2.0, 0.0));
+        assert_eq!(flat.closest_points(&across).distance_squared, 0.0);
+    }
+
+    #[test]
+    fn test_closest_point_on_triangle() {
+        let triangle = Triangle::new(v3(0.0, 0.0, 0.0), v3(4.0, 0.0, 0.0), v3(0.0, 4.0, 0.0));
+        // Interior, each vertex region and each edge region
+        let cases = [
+            (v3(1.0, 1.0, 5.0), v3(1.0, 1.0, 0.0)),
+            (v3(-1.0, -1.0, 0.0), v3(0.0, 0.0, 0.0)),
+            (v3(6.0, -1.0, 0.0), v3(4.0, 0.0, 0.0)),
+            (v3(-1.0, 6.0, 1.0), v3(0.0, 4.0, 0.0)),
+            (v3(2.0, -3.0, 0.0), v3(2.0, 0.0, 0.0)),
+            (v3(-3.0, 2.0, 0.0), v3(0.0, 2.0, 0.0)),
+            (v3(3.0, 3.0, 0.0), v3(2.0, 2.0, 0.0)),
+        ];
+        for (query, expected) in cases {
+            let closest = triangle.closest_point(query);
+            assert_vec_near(closest.point, expected, 1e-12);
+            assert!((closest.distance_squared - (query - expected).length_squared()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_closest_point_on_boxes() {
+        let turned = Quaternion::from_axis_angle(v3(0.0, 0.0, 1.0), core::f64::consts::FRAC_PI_4);
+        let obb = Obb::new(v3(10.0, 0.0, 0.0), v3(1.0, 1.0, 1.0), turned);
+        let closest = obb.closest_point(v3(0.0, 0.0, 0.0));
+        assert_vec_near(closest.point, v3(10.0 - 2.0f64.sqrt(), 0.0, 0.0), 1e-12);
+
+        let obb2 = Obb2::new(
+            Vector2f64::new(10.0, 0.0),
+            Vector2f64::new(1.0, 1.0),
+            core::f64::consts::FRAC_PI_4,
+        );
+        let closest = obb2.closest_point(Vector2f64::new(0.0, 0.0));
+        assert!((closest.point[0] - (10.0 - 2.0f64.sqrt())).abs() < 1e-12);
+        assert!(closest.point[1].abs() < 1e-12);
+    }
+}
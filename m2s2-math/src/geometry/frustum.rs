@@ -0,0 +1,233 @@
+use core::array;
+
+use num_traits::Float;
+
+use crate::geometry::{Aabb3, Plane};
+use crate::matrix::Matrix4x4;
+use crate::matrix::transfom_traits::{Convention, DepthRange, VectorConvention};
+use crate::vector::Vector3;
+
+/// Result of classifying a volume against a frustum
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Containment {
+    Outside,
+    Intersecting,
+    Inside,
+}
+
+/// View frustum as six planes with unit normals pointing inward.
+///
+/// Planes are stored as left, right, bottom, top, near, far. The sphere and box tests are
+/// conservative: volumes near a frustum corner can be reported as intersecting when they
+/// are just outside.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frustum<T> {
+    planes: [Plane<T>; 6],
+}
+
+impl<T: Float> Frustum<T> {
+    pub fn new(planes: [Plane<T>; 6]) -> Self {
+        Frustum { planes }
+    }
+
+    /// Extract the planes of a projection or view-projection matrix (Gribb-Hartmann).
+    ///
+    /// The convention must match the one the projection was built with. For an infinite
+    /// far plane the far plane has a zero normal and contains everything.
+    pub fn from_matrix(m: &Matrix4x4<T>, convention: Convention) -> Self {
+        let m = match convention.vectors {
+            VectorConvention::Column => *m,
+            VectorConvention::Row => m.transpose(),
+        };
+        let row = |i: usize| [m[i][0], m[i][1], m[i][2], m[i][3]];
+        let (x, y, z, w) = (row(0), row(1), row(2), row(3));
+        let add = |a: [T; 4], b: [T; 4]| array::from_fn(|i| a[i] + b[i]);
+        let sub = |a: [T; 4], b: [T; 4]| array::from_fn(|i| a[i] - b[i]);
+
+        // Clip z bounds: the low end of the depth range is near unless reverse-Z is on
+        let low = match convention.depth {
+            DepthRange::ZeroToOne => z,
+            DepthRange::NegativeOneToOne => add(w, z),
+        };
+        let high = sub(w, z);
+        let (near, far) = if convention.reverse_z {
+            (high, low)
+        } else {
+            (low, high)
+        };
+
+        let plane = |p: [T; 4]| Plane::new(Vector3::new(p[0], p[1], p[2]), p[3]).normalize();
+        Frustum {
+            planes: [
+                plane(add(w, x)),
+                plane(sub(w, x)),
+                plane(add(w, y)),
+                plane(sub(w, y)),
+                plane(near),
+                plane(far),
+            ],
+        }
+    }
+
+    /// The planes in the order left, right, bottom, top, near, far
+    pub fn planes(&self) -> &[Plane<T>; 6] {
+        &self.planes
+    }
+
+    pub fn near(&self) -> &Plane<T> {
+        &self.planes[4]
+    }
+
+    pub fn far(&self) -> &Plane<T> {
+        &self.planes[5]
+    }
+
+    pub fn contains_point(&self, point: Vector3<T>) -> bool {
+        self.planes
+            .iter()
+            .all(|plane| plane.signed_distance(point) >= T::zero())
+    }
+
+    pub fn classify_sphere(&self, center: Vector3<T>, radius: T) -> Containment {
+        let mut result = Containment::Inside;
+        for plane in &self.planes {
+            let distance = plane.signed_distance(center);
+            if distance < -radius {
+                return Containment::Outside;
+            }
+            if distance < radius {
+                result = Containment::Intersecting;
+            }
+        }
+        result
+    }
+
+    pub fn intersects_sphere(&self, center: Vector3<T>, radius: T) -> bool {
+        self.classify_sphere(center, radius) != Containment::Outside
+    }
+
+    /// Classify an axis-aligned box against the frustum
+    pub fn classify_aabb(&self, aabb: &Aabb3<T>) -> Containment {
+        let mut result = Containment::Inside;
+        for plane in &self.planes {
+            // Corners furthest along (positive) and against (negative) the normal
+            let n = plane.normal;
+            let corner = |toward: bool| {
+                Vector3::from_array(array::from_fn(|i| {
+                    if (n[i] >= T::zero()) == toward {
+                        aabb.max[i]
+                    } else {
+                        aabb.min[i]
+                    }
+                }))
+            };
+            if plane.signed_distance(corner(true)) < T::zero() {
+                return Containment::Outside;
+            }
+            if plane.signed_distance(corner(false)) < T::zero() {
+                result = Containment::Intersecting;
+            }
+        }
+        result
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb3<T>) -> bool {
+        self.classify_aabb(aabb) != Containment::Outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Containment, Frustum};
+    use crate::Matrix4x4f64;
+    use crate::geometry::Aabb3f64;
+    use crate::matrix::transfom_traits::{Convention, Transform4x4, VectorConvention};
+    use crate::vector::Vector3f64;
+    use crate::vector::vector_ops::Vector3Ops;
+
+    // Camera at (0, 0, 5) looking at the origin, 90 degree field of view, near 1, far 11
+    fn view_projection(convention: Convention) -> Matrix4x4f64 {
+        let eye = Vector3f64::new(0.0, 0.0, 5.0);
+        let target = Vector3f64::new(0.0, 0.0, 0.0);
+        let up = Vector3f64::new(0.0, 1.0, 0.0);
+        let fov = core::f64::consts::FRAC_PI_2;
+        let projection = Matrix4x4f64::perspective_with(fov, 1.0, 1.0, 11.0, convention);
+        let view = Matrix4x4f64::look_at_with(eye, target, up, convention);
+        match convention.vectors {
+            VectorConvention::Column => projection * view,
+            VectorConvention::Row => view * projection,
+        }
+    }
+
+    #[test]
+    fn test_frustum_planes() {
+        let conventions = [
+            Convention::default(),
+            Convention::OPENGL,
+            Convention::LEFT_HANDED_ROW,
+            Convention::default().with_reverse_z(true),
+        ];
+        for convention in conventions {
+            let frustum = Frustum::from_matrix(&view_projection(convention), convention);
+
+            // The near plane is 1 unit in front of the camera, the far plane 11
+            let near = frustum.near();
+            let far = frustum.far();
+            assert!((near.signed_distance(Vector3f64::new(0.0, 0.0, 4.0))).abs() < 1e-12);
+            assert!((far.signed_distance(Vector3f64::new(0.0, 0.0, -6.0))).abs() < 1e-12);
+            for plane in frustum.planes() {
+                assert!((plane.normal.length() - 1.0).abs() < 1e-12);
+            }
+
+            assert!(frustum.contains_point(Vector3f64::new(0.0, 0.0, 0.0)));
+            assert!(frustum.contains_point(Vector3f64::new(4.9, 0.0, 0.0)));
+            assert!(!frustum.contains_point(Vector3f64::new(5.1, 0.0, 0.0)));
+            assert!(!frustum.contains_point(Vector3f64::new(0.0, 0.0, 4.5)));
+            assert!(!frustum.contains_point(Vector3f64::new(0.0, 0.0, -6.5)));
+        }
+    }
+
+    #[test]
+    fn test_frustum_spheres_and_boxes() {
+        let convention = Convention::default();
+        let frustum = Frustum::from_matrix(&view_projection(convention), convention);
+        let origin = Vector3f64::new(0.0, 0.0, 0.0);
+
+        assert_eq!(frustum.classify_sphere(origin, 1.0), Containment::Inside);
+        assert_eq!(
+            frustum.classify_sphere(Vector3f64::new(0.0, 0.0, -6.0), 1.0),
+            Containment::Intersecting
+        );
+        assert_eq!(
+            frustum.classify_sphere(Vector3f64::new(20.0, 0.0, 0.0), 1.0),
+            Containment::Outside
+        );
+        assert!(!frustum.intersects_sphere(Vector3f64::new(0.0, 0.0, 7.0), 1.0));
+
+        let aabb = |min: [f64; 3], max: [f64; 3]| {
+            Aabb3f64::new(Vector3f64::from_array(min), Vector3f64::from_array(max))
+        };
+        assert_eq!(
+            frustum.classify_aabb(&aabb([-1.0, -1.0, -1.0], [1.0, 1.0, 1.0])),
+            Containment::Inside
+        );
+        assert_eq!(
+            frustum.classify_aabb(&aabb([4.0, -1.0, -1.0], [6.0, 1.0, 1.0])),
+            Containment::Intersecting
+        );
+        assert_eq!(
+            frustum.classify_aabb(&aabb([-3.0, -3.0, 5.0], [3.0, 3.0, 8.0])),
+            Containment::Outside
+        );
+        assert!(frustum.intersects_aabb(&aabb([-50.0; 3], [50.0; 3])));
+    }
+
+    #[test]
+    fn test_frustum_infinite_far() {
+        let convention = Convention::default().with_reverse_z(true);
+        let projection = Matrix4x4f64::perspective_infinite_with(1.0, 1.0, 0.1, convention);
+        let frustum = Frustum::from_matrix(&projection, convention);
+        assert!(frustum.contains_point(Vector3f64::new(0.0, 0.0, -1e12)));
+        assert!(!frustum.contains_point(Vector3f64::new(0.0, 0.0, -0.05)));
+    }
+}
@@ -0,0 +1,587 @@
+use num_traits::Float;
+
+use crate::geometry::ClosestPoints;
+use crate::geometry::closest::triangle_closest_weights;
+use crate::geometry::support::{SupportMap, unit_or_zero};
+use crate::vector::Vector3;
+use crate::vector::vector_ops::Vector3Ops;
+
+const MAX_GJK_ITERATIONS: usize = 64;
+const MAX_EPA_VERTICES: usize = 64;
+const MAX_EPA_FACES: usize = 2 * MAX_EPA_VERTICES;
+const MAX_EPA_EDGES: usize = MAX_EPA_FACES;
+
+/// Overlap of two convex shapes found by `epa_penetration`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Penetration<T> {
+    /// Unit direction from the first shape into the second. Moving the second shape by
+    /// `normal * depth` separates them.
+    pub normal: Vector3<T>,
+    pub depth: T,
+    /// Point of the first shape deepest inside the second
+    pub first: Vector3<T>,
+    /// Point of the second shape deepest inside the first
+    pub second: Vector3<T>,
+}
+
+fn origin<T: Float>() -> Vector3<T> {
+    Vector3::new(T::zero(), T::zero(), T::zero())
+}
+
+// Vertex of the Minkowski difference `a - b`, keeping the points of each shape it came from
+#[derive(Debug, Clone, Copy)]
+struct SupportPoint<T> {
+    w: Vector3<T>,
+    a: Vector3<T>,
+    b: Vector3<T>,
+}
+
+fn support_point<T: Float>(
+    a: &impl SupportMap<T>,
+    b: &impl SupportMap<T>,
+    direction: Vector3<T>,
+) -> SupportPoint<T> {
+    let (a, b) = (a.support(direction), b.support(-direction));
+    SupportPoint { w: a - b, a, b }
+}
+
+// Up to four support points with the barycentric weights of the simplex point closest to
+// the origin
+#[derive(Debug, Clone, Copy)]
+struct Simplex<T> {
+    points: [SupportPoint<T>; 4],
+    weights: [T; 4],
+    len: usize,
+}
+
+impl<T: Float> Simplex<T> {
+    fn push(&mut self, point: SupportPoint<T>) {
+        self.points[self.len] = point;
+        self.len += 1;
+    }
+
+    fn max_norm_squared(&self) -> T {
+        self.points[..self.len]
+            .iter()
+            .fold(T::zero(), |max, p| max.max(p.w.length_squared()))
+    }
+
+    // Move to the sub-simplex nearest the origin and return the closest point on it. A
+    // tetrahedron enclosing the origin is kept whole and gives the zero vector.
+    fn reduce(&mut self) -> Vector3<T> {
+        let (zero, one) = (T::zero(), T::one());
+        let p: [Vector3<T>; 4] = core::array::from_fn(|i| self.points[i].w);
+        let weights = match self.len {
+            1 => [one, zero, zero, zero],
+            2 => {
+                let edge = p[1] - p[0];
+                let length_squared = edge.length_squared();
+                let t = if length_squared > zero {
+                    (-p[0].dot(&edge) / length_squared).max(zero).min(one)
+                } else {
+                    zero
+                };
+                [one - t, t, zero, zero]
+            }
+            3 => {
+                let [u, v, w] = triangle_closest_weights(p[0], p[1], p[2], origin());
+                [u, v, w, zero]
+            }
+            _ => match closest_tetrahedron_face(&p) {
+                Some(weights) => weights,
+                None => {
+                    self.weights = [one; 4];
+                    return origin();
+                }
+            },
+        };
+
+        let mut len = 0;
+        for (i, &weight) in weights.iter().enumerate().take(self.len) {
+            if weight > zero {
+                self.points[len] = self.points[i];
+                self.weights[len] = weight;
+                len += 1;
+            }
+        }
+        self.len = len;
+        self.combine(|p| p.w)
+    }
+
+    fn combine(&self, pick: impl Fn(&SupportPoint<T>) -> Vector3<T>) -> Vector3<T> {
+        self.points[..self.len]
+            .iter()
+            .zip(self.weights)
+            .fold(origin(), |sum, (p, weight)| sum + pick(p) * weight)
+    }
+}
+
+// Weights of the closest point on the faces of tetrahedron `p` that face the origin, `None`
+// when the origin is inside
+fn closest_tetrahedron_face<T: Float>(p: &[Vector3<T>; 4]) -> Option<[T; 4]> {
+    const FACES: [[usize; 4]; 4] = [[0, 1, 2, 3], [0, 1, 3, 2], [0, 2, 3, 1], [1, 2, 3, 0]];
+    let mut best: Option<(T, [T; 4])> = None;
+    for [i, j, k, opposite] in FACES {
+        let normal = (p[j] - p[i]).cross(&(p[k] - p[i]));
+        let origin_side = -normal.dot(&p[i]);
+        let opposite_side = normal.dot(&(p[opposite] - p[i]));
+        // A flat tetrahedron has no inside, so every face is a candidate
+        if origin_side * opposite_side > T::zero() {
+            continue;
+        }
+        let [u, v, w] = triangle_closest_weights(p[i], p[j], p[k], origin());
+        let point = p[i] * u + p[j] * v + p[k] * w;
+        let distance_squared = point.length_squared();
+        if best.is_none_or(|(closest, _)| distance_squared < closest) {
+            let mut weights = [T::zero(); 4];
+            (weights[i], weights[j], weights[k]) = (u, v, w);
+            best = Some((distance_squared, weights));
+        }
+    }
+    best.map(|(_, weights)| weights)
+}
+
+enum Gjk<T> {
+    Separated(ClosestPoints<T, 3>),
+    Intersecting(Simplex<T>),
+}
+
+// Gilbert-Johnson-Keerthi: walk a simplex of the Minkowski difference towards the origin
+fn gjk<T: Float>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> Gjk<T> {
+    let first = support_point(a, b, Vector3::new(T::one(), T::zero(), T::zero()));
+    let mut simplex = Simplex {
+        points: [first; 4],
+        weights: [T::one(), T::zero(), T::zero(), T::zero()],
+        len: 1,
+    };
+    let tolerance = T::epsilon().sqrt();
+    let mut v = first.w;
+
+    for _ in 0..MAX_GJK_ITERATIONS {
+        let v_squared = v.length_squared();
+        if v_squared <= T::epsilon() * simplex.max_norm_squared() {
+            return Gjk::Intersecting(simplex);
+        }
+        let w = support_point(a, b, -v);
+        // The support point is no closer than the current estimate: converged
+        if v_squared - v.dot(&w.w) <= tolerance * v_squared {
+            break;
+        }
+        let previous = simplex;
+        simplex.push(w);
+        let next = simplex.reduce();
+        // A support point beyond the origin along -v proves the shapes apart, so a
+        // tetrahedron around the origin would only come from rounding
+        let apart = v.dot(&w.w) > T::epsilon() * simplex.max_norm_squared();
+        if simplex.len == 4 && !apart {
+            return Gjk::Intersecting(simplex);
+        }
+        // Rounding stopped the simplex getting closer: keep the closest one found
+        if simplex.len == 4 || next.length_squared() >= v_squared {
+            simplex = previous;
+            break;
+        }
+        v = next;
+    }
+
+    let (first, second) = (simplex.combine(|p| p.a), simplex.combine(|p| p.b));
+    let offset = first - second;
+    Gjk::Separated(ClosestPoints {
+        first,
+        second,
+        distance_squared: offset.length_squared(),
+    })
+}
+
+/// Closest points between two convex shapes, `first` on `a` and `second` on `b`. `None`
+/// when the shapes overlap or touch.
+pub fn gjk_distance<T: Float>(
+    a: &impl SupportMap<T>,
+    b: &impl SupportMap<T>,
+) -> Option<ClosestPoints<T, 3>> {
+    match gjk(a, b) {
+        Gjk::Separated(closest) => Some(closest),
+        Gjk::Intersecting(_) => None,
+    }
+}
+
+/// True when two convex shapes overlap, including touching
+pub fn gjk_intersects<T: Float>(a: &impl SupportMap<T>, b: &impl SupportMap<T>) -> bool {
+    matches!(gjk(a, b), Gjk::Intersecting(_))
+}
+
+// Grow the simplex GJK stopped on into a tetrahedron of the Minkowski difference that still
+// contains the origin. `None` when the difference is flat.
+fn expand_to_tetrahedron<T: Float>(
+    simplex: &Simplex<T>,
+    a: &impl SupportMap<T>,
+    b: &impl SupportMap<T>,
+) -> Option<[SupportPoint<T>; 4]> {
+    let mut points = simplex.points;
+    let mut len = simplex.len;
+    let scale = simplex.max_norm_squared() * T::epsilon();
+    let (zero, one) = (T::zero(), T::one());
+    let axes = [
+        Vector3::new(one, zero, zero),
+        Vector3::new(zero, one, zero),
+        Vector3::new(zero, zero, one),
+    ];
+
+    if len == 1 {
+        let p0 = points[0].w;
+        points[1] = axes
+            .iter()
+            .flat_map(|&axis| [axis, -axis])
+            .map(|direction| support_point(a, b, direction))
+            .find(|p| (p.w - p0).length_squared() > scale)?;
+        len = 2;
+    }
+    if len == 2 {
+        let (p0, edge) = (points[0].w, points[1].w - points[0].w);
+        let axis = (0..3)
+            .min_by(|&i, &j| edge[i].abs().partial_cmp(&edge[j].abs()).unwrap())
+            .map_or(axes[0], |i| axes[i]);
+        let side = edge.cross(&axis);
+        let up = edge.cross(&side);
+        points[2] = [side, -side, up, -up]
+            .into_iter()
+            .map(|direction| support_point(a, b, direction))
+            .find(|p| (p.w - p0).cross(&edge).length_squared() > scale * edge.length_squared())?;
+        len = 3;
+    }
+    if len == 3 {
+        let p0 = points[0].w;
+        let normal = (points[1].w - p0).cross(&(points[2].w - p0));
+        points[3] = [normal, -normal]
+            .into_iter()
+            .map(|direction| support_point(a, b, direction))
+            .find(|p| {
+                let height = normal.dot(&(p.w - p0));
+                height * height > scale * normal.length_squared()
+            })?;
+    }
+    Some(points)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Face<T> {
+    vertices: [usize; 3],
+    normal: Vector3<T>,
+    distance: T,
+}
+
+impl<T: Float> Face<T> {
+    // Face with its normal along (j - i) x (k - i). Slivers get an infinite distance so
+    // they are never chosen.
+    fn new(points: &[SupportPoint<T>], i: usize, j: usize, k: usize) -> Self {
+        let normal = (points[j].w - points[i].w).cross(&(points[k].w - points[i].w));
+        let length = normal.length();
+        let (normal, distance) = if length > T::zero() {
+            let normal = normal * (T::one() / length);
+            (normal, normal.dot(&points[i].w))
+        } else {
+            (normal, T::infinity())
+        };
+        Face {
+            vertices: [i, j, k],
+            normal,
+            distance,
+        }
+    }
+}
+
+/// Penetration depth and contact normal of two overlapping convex shapes, found by the
+/// expanding polytope algorithm. `None` when the shapes are separated, or when both are
+/// flat in a shared plane so the overlap has no volume.
+///
+/// Curved shapes may not converge within the polytope's fixed capacity. The result is then
+/// the best direction found, whose depth always separates the shapes but may slightly
+/// overestimate the smallest one.
+pub fn epa_penetration<T: Float>(
+    a: &impl SupportMap<T>,
+    b: &impl SupportMap<T>,
+) -> Option<Penetration<T>> {
+    let simplex = match gjk(a, b) {
+        Gjk::Separated(_) => return None,
+        Gjk::Intersecting(simplex) => simplex,
+    };
+    let tetrahedron = expand_to_tetrahedron(&simplex, a, b)?;
+
+    let mut vertices = [tetrahedron[0]; MAX_EPA_VERTICES];
+    vertices[..4].copy_from_slice(&tetrahedron);
+    let mut vertex_count = 4;
+
+    // Wind every face of the tetrahedron so its normal points away from the centroid
+    let quarter = T::one() / T::from(4).unwrap();
+    let centroid = tetrahedron
+        .iter()
+        .fold(origin(), |sum, p| sum + p.w * quarter);
+    let mut faces = [Face::new(&vertices, 0, 1, 2); MAX_EPA_FACES];
+    for (slot, [i, j, k]) in [[0, 1, 2], [0, 3, 1], [0, 2, 3], [1, 3, 2]]
+        .into_iter()
+        .enumerate()
+    {
+        let face = Face::new(&vertices, i, j, k);
+        faces[slot] = if face.normal.dot(&(centroid - vertices[i].w)) > T::zero() {
+            Face::new(&vertices, i, k, j)
+        } else {
+            face
+        };
+    }
+    let mut face_count = 4;
+
+    let tolerance = T::epsilon().sqrt();
+    let mut edges = [[0usize; 2]; MAX_EPA_EDGES];
+    // The closest face distance is a lower bound on the depth and the support distance along
+    // its normal an upper bound. The smallest upper bound seen is kept for when the polytope
+    // runs out of room before the two meet.
+    let mut upper: Option<(T, Vector3<T>, SupportPoint<T>)> = None;
+    let mut previous = None;
+    let converged = loop {
+        let closest = *faces[..face_count]
+            .iter()
+            .min_by(|x, y| x.distance.partial_cmp(&y.distance).unwrap())
+            .unwrap();
+        if vertex_count == MAX_EPA_VERTICES {
+            break None;
+        }
+        let w = support_point(a, b, closest.normal);
+        let bound = w.w.dot(&closest.normal);
+        if upper.is_none_or(|(depth, _, _)| bound < depth) {
+            previous = upper;
+            upper = Some((bound, closest.normal, w));
+        }
+        let scale = vertices[..vertex_count]
+            .iter()
+            .fold(w.w.length(), |max, p| max.max(p.w.length()));
+        if bound - closest.distance <= tolerance * scale {
+            break Some(closest);
+        }
+
+        // Remove every face the new vertex sees, keeping the edges on the boundary of the
+        // hole
+        let mut edge_count = 0;
+        let mut f = face_count;
+        while f > 0 {
+            f -= 1;
+            let face = faces[f];
+            if face.normal.dot(&(w.w - vertices[face.vertices[0]].w)) <= T::zero() {
+                continue;
+            }
+            for e in 0..3 {
+                let edge = [face.vertices[e], face.vertices[(e + 1) % 3]];
+                let shared = (0..edge_count).find(|&s| edges[s] == [edge[1], edge[0]]);
+                if let Some(s) = shared {
+                    edge_count -= 1;
+                    edges[s] = edges[edge_count];
+                } else if edge_count == MAX_EPA_EDGES {
+                    break;
+                } else {
+                    edges[edge_count] = edge;
+                    edge_count += 1;
+                }
+            }
+            face_count -= 1;
+            faces[f] = faces[face_count];
+        }
+        if face_count + edge_count > MAX_EPA_FACES {
+            break None;
+        }
+
+        vertices[vertex_count] = w;
+        for &[i, j] in &edges[..edge_count] {
+            faces[face_count] = Face::new(&vertices, i, j, vertex_count);
+            face_count += 1;
+        }
+        vertex_count += 1;
+    };
+
+    let Some(closest) = converged else {
+        let (mut depth, mut normal, mut w) = upper?;
+        // Refine the best direction by fitting a sphere to the boundary through the last two
+        // support points and aiming at its point nearest the origin, exact for round shapes.
+        // Only steps that lower the bound are taken.
+        if let Some((_, mut last_normal, mut last)) = previous {
+            for _ in 0..MAX_GJK_ITERATIONS {
+                let turn = (normal - last_normal).length();
+                if turn <= T::epsilon() {
+                    break;
+                }
+                let radius = (w.w - last.w).length() / turn;
+                let direction = unit_or_zero(normal * radius - w.w);
+                let next = support_point(a, b, direction);
+                let bound = next.w.dot(&direction);
+                if bound >= depth || bound.is_nan() {
+                    break;
+                }
+                (last_normal, last) = (normal, w);
+                (depth, normal, w) = (bound, direction, next);
+            }
+        }
+        // The support points are the extremes of each shape along the normal
+        return Some(Penetration {
+            normal,
+            depth: depth.max(T::zero()),
+            first: w.a,
+            second: w.b,
+        });
+    };
+
+    let [i, j, k] = closest.vertices;
+    let depth = closest.distance.max(T::zero());
+    let [u, v, w] = triangle_closest_weights(
+        vertices[i].w,
+        vertices[j].w,
+        vertices[k].w,
+        closest.normal * depth,
+    );
+    Some(Penetration {
+        normal: closest.normal,
+        depth,
+        first: vertices[i].a * u + vertices[j].a * v + vertices[k].a * w,
+        second: vertices[i].b * u + vertices[j].b * v + vertices[k].b * w,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{epa_penetration, gjk_distance, gjk_intersects};
+    use crate::geometry::{Aabb3f64, Capsule, ConvexHull, Obb, Sphere};
+    use crate::quaternian::Quaternion;
+    use crate::test_util::{assert_vec_near, v3};
+    use crate::vector::Vector3f64;
+    use crate::vector::vector_ops::Vector3Ops;
+
+    fn unit_box(center: Vector3f64) -> Aabb3f64 {
+        Aabb3f64::from_center_half_extents(center, v3(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn test_gjk_distance_between_separated_shapes() {
+        let a = Sphere::new(v3(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(v3(5.0, 0.0, 0.0), 2.0);
+        let closest = gjk_distance(&a, &b).unwrap();
+        assert!((closest.distance_squared.sqrt() - 2.0).abs() < 1e-6);
+        assert_vec_near(closest.first, v3(1.0, 0.0, 0.0), 1e-4);
+        assert_vec_near(closest.second, v3(3.0, 0.0, 0.0), 1e-4);
+
+        let boxes = gjk_distance(&unit_box(v3(0.0, 0.0, 0.0)), &unit_box(v3(4.0, 0.5, 0.0)));
+        assert!((boxes.unwrap().distance_squared - 4.0).abs() < 1e-9);
+
+        // Box corner pointing at a tetrahedron face
+        let points = [
+            v3(3.0, 3.0, 3.0),
+            v3(5.0, 3.0, 3.0),
+            v3(3.0, 5.0, 3.0),
+            v3(3.0, 3.0, 5.0),
+        ];
+        let closest = gjk_distance(&unit_box(v3(0.0, 0.0, 0.0)), &ConvexHull::new(&points));
+        let closest = closest.unwrap();
+        assert_vec_near(closest.first, v3(1.0, 1.0, 1.0), 1e-9);
+        assert_vec_near(closest.second, v3(3.0, 3.0, 3.0), 1e-9);
+
+        // Spheres nearly touching a box edge, where rounding stalls the simplex or closes
+        // it into a flat tetrahedron around the origin
+        let sphere = Sphere::new(
+            v3(-0.5313503864784551, 0.2604325901300082, 1.711292714524749),
+            0.4924032646030585,
+        );
+        let aabb = Aabb3f64::new(
+            v3(-0.8040019363890949, -1.9931709837227671, -1.051898725759572),
+            v3(1.0534546730409917, 0.02133473407360742, 1.280835758996889),
+        );
+        let closest = gjk_distance(&sphere, &aabb).unwrap();
+        assert!(closest.distance_squared.sqrt() < 1e-6);
+
+        let sphere = Sphere::new(
+            v3(1.1958980216650135, -0.8740969038237397, 1.82769050567462),
+            0.6415106768577684,
+        );
+        let aabb = Aabb3f64::new(
+            v3(
+                -0.007102139197569413,
+                -0.7522404302721137,
+                -0.5009542347979599,
+            ),
+            v3(0.5659287820606236, 0.5555446833485649, 2.097033189654069),
+        );
+        let closest = gjk_distance(&sphere, &aabb).unwrap();
+        assert!((closest.distance_squared.sqrt() - 1.358325180e-4).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "ConvexHull needs at least one point")]
+    fn test_empty_convex_hull() {
+        ConvexHull::<f64>::new(&[]);
+    }
+
+    #[test]
+    fn test_gjk_intersects() {
+        let capsule = Capsule::new(v3(-3.0, 2.0, 0.0), v3(3.0, 2.0, 0.0), 1.5);
+        assert!(gjk_intersects(&capsule, &unit_box(v3(0.0, 0.0, 0.0))));
+        assert!(!gjk_intersects(&capsule, &unit_box(v3(0.0, -1.0, 0.0))));
+        assert!(gjk_distance(&capsule, &unit_box(v3(0.0, 0.0, 0.0))).is_none());
+
+        let turned = Quaternion::from_axis_angle(v3(0.0, 0.0, 1.0), core::f64::consts::FRAC_PI_4);
+        let obb = Obb::new(v3(2.3, 0.0, 0.0), v3(1.0, 1.0, 1.0), turned);
+        assert!(gjk_intersects(&obb, &unit_box(v3(0.0, 0.0, 0.0))));
+        let obb = Obb::new(v3(2.5, 0.0, 0.0), v3(1.0, 1.0, 1.0), turned);
+        assert!(!gjk_intersects(&obb, &unit_box(v3(0.0, 0.0, 0.0))));
+    }
+
+    #[test]
+    fn test_epa_penetration() {
+        let a = Sphere::new(v3(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(v3(1.5, 0.0, 0.0), 1.0);
+        let penetration = epa_penetration(&a, &b).unwrap();
+        assert!((penetration.depth - 0.5).abs() < 1e-3);
+        assert_vec_near(penetration.normal, v3(1.0, 0.0, 0.0), 1e-2);
+        assert_vec_near(penetration.first, v3(1.0, 0.0, 0.0), 1e-2);
+        assert_vec_near(penetration.second, v3(0.5, 0.0, 0.0), 1e-2);
+
+        let penetration =
+            epa_penetration(&unit_box(v3(0.0, 0.0, 0.0)), &unit_box(v3(0.2, 1.7, -0.1))).unwrap();
+        assert!((penetration.depth - 0.3).abs() < 1e-9);
+        assert_vec_near(penetration.normal, v3(0.0, 1.0, 0.0), 1e-9);
+
+        // Moving the second shape by the normal times the depth leaves them just touching
+        let moved = unit_box(v3(0.2, 1.7, -0.1) + penetration.normal * (penetration.depth + 1e-6));
+        assert!(!gjk_intersects(&unit_box(v3(0.0, 0.0, 0.0)), &moved));
+
+        assert!(epa_penetration(&a, &Sphere::new(v3(3.0, 0.0, 0.0), 0.5)).is_none());
+    }
+
+    #[test]
+    fn test_epa_deep_penetration_of_round_shapes() {
+        // Deep overlaps of curved shapes fill the polytope before converging
+        let a = Sphere::new(v3(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(v3(0.12, 0.16, 0.0), 1.0);
+        let penetration = epa_penetration(&a, &b).unwrap();
+        assert!((penetration.depth - 1.8).abs() < 1e-6);
+        assert_vec_near(penetration.normal, v3(0.6, 0.8, 0.0), 1e-6);
+        assert_vec_near(penetration.first, v3(0.6, 0.8, 0.0), 1e-6);
+        assert_vec_near(penetration.second, v3(-0.48, -0.64, 0.0), 1e-6);
+
+        // Any direction will do for concentric spheres, but the depth must be exact
+        let penetration = epa_penetration(&a, &a).unwrap();
+        assert!((penetration.depth - 2.0).abs() < 1e-9);
+        assert!((penetration.normal.length() - 1.0).abs() < 1e-9);
+
+        let capsule = Capsule::new(v3(-2.0, 0.0, 0.0), v3(2.0, 0.0, 0.0), 1.0);
+        let ball = Sphere::new(v3(2.5, 0.0, 0.5), 1.0);
+        let penetration = epa_penetration(&capsule, &ball).unwrap();
+        let diagonal = 0.5f64.sqrt();
+        assert!((penetration.depth - (2.0 - diagonal)).abs() < 1e-6);
+        assert_vec_near(penetration.normal, v3(diagonal, 0.0, diagonal), 1e-6);
+
+        let inside = Sphere::new(v3(0.5, 0.3, 0.0), 1.0);
+        let penetration = epa_penetration(&capsule, &inside).unwrap();
+        assert!((penetration.depth - 1.7).abs() < 1e-6);
+        assert_vec_near(penetration.normal, v3(0.0, 1.0, 0.0), 1e-3);
+        let moved = Sphere::new(
+            inside.center + penetration.normal * (penetration.depth + 1e-6),
+            1.0,
+        );
+        assert!(!gjk_intersects(&capsule, &moved));
+    }
+}
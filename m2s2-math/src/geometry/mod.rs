@@ -0,0 +1,40 @@
+mod aabb;
+mod capsule;
+mod closest;
+mod frustum;
+mod gjk;
+mod obb;
+mod overlap;
+mod plane;
+mod ray;
+mod segment;
+mod sphere;
+mod support;
+mod sweep;
+mod triangle;
+mod viewport;
+
+pub use aabb::{Aabb, Aabb2, Aabb2f32, Aabb2f64, Aabb3, Aabb3f32, Aabb3f64};
+pub use capsule::{Capsule, Capsule2, Capsule3};
+pub use closest::{ClosestPoints, PointQuery};
+pub use frustum::{Containment, Frustum};
+pub use gjk::{Penetration, epa_penetration, gjk_distance, gjk_intersects};
+pub use obb::{Obb, Obb2};
+pub use plane::Plane;
+pub use ray::{Ray, Ray2, Ray2f32, Ray2f64, Ray3, Ray3f32, Ray3f64, RayHit, TriangleHit};
+pub use segment::{Segment, Segment2, Segment3};
+pub use sphere::{Circle, Sphere};
+pub use support::{ConvexHull, SupportMap};
+pub use sweep::{TimeOfImpact, time_of_impact};
+pub use triangle::{Triangle, Triangle2};
+pub use viewport::Viewport;
+
+use num_traits::Float;
+
+use crate::vector::Vector;
+
+// Dot product in any dimension, for code generic over `D` (`Vector2Ops`/`Vector3Ops` cover
+// only 2D and 3D)
+pub(crate) fn dot<T: Float, const D: usize>(a: &Vector<T, D>, b: &Vector<T, D>) -> T {
+    (0..D).fold(T::zero(), |sum, i| sum + a[i] * b[i])
+}
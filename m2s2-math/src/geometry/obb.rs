@@ -0,0 +1,73 @@
+use num_traits::Float;
+
+use crate::quaternian::Quaternion;
+use crate::vector::vector_ops::Vector2Ops;
+use crate::vector::{Vector2, Vector3};
+
+/// Oriented bounding box: a box of `half_extents` rotated by `rotation` about `center`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb<T> {
+    pub center: Vector3<T>,
+    pub half_extents: Vector3<T>,
+    pub rotation: Quaternion<T>,
+}
+
+impl<T: Float> Obb<T> {
+    pub fn new(center: Vector3<T>, half_extents: Vector3<T>, rotation: Quaternion<T>) -> Self {
+        Obb {
+            center,
+            half_extents,
+            rotation,
+        }
+    }
+
+    /// World direction of local axis `i` (0 = X, 1 = Y, 2 = Z)
+    pub fn axis(&self, i: usize) -> Vector3<T> {
+        let mut unit = Vector3::from_array([T::zero(); 3]);
+        unit[i] = T::one();
+        self.rotation.rotate_vector(unit)
+    }
+
+    /// Express a world point in the box's local frame, centered on the box
+    pub fn to_local(&self, point: Vector3<T>) -> Vector3<T> {
+        self.rotation.conjugate().rotate_vector(point - self.center)
+    }
+
+    pub fn contains_point(&self, point: Vector3<T>) -> bool {
+        let local = self.to_local(point);
+        (0..3).all(|i| local[i].abs() <= self.half_extents[i])
+    }
+}
+
+/// 2D oriented box: a box of `half_extents` rotated counter-clockwise by `angle` radians
+/// about `center`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Obb2<T> {
+    pub center: Vector2<T>,
+    pub half_extents: Vector2<T>,
+    pub angle: T,
+}
+
+impl<T: Float> Obb2<T> {
+    pub fn new(center: Vector2<T>, half_extents: Vector2<T>, angle: T) -> Self {
+        Obb2 {
+            center,
+            half_extents,
+            angle,
+        }
+    }
+
+    /// World directions of the local X and Y axes
+    pub fn axes(&self) -> [Vector2<T>; 2] {
+        let (sin, cos) = self.angle.sin_cos();
+        [Vector2::new(cos, sin), Vector2::new(-sin, cos)]
+    }
+
+    pub fn contains_point(&self, point: Vector2<T>) -> bool {
+        let offset = point - self.center;
+        self.axes()
+            .iter()
+            .enumerate()
+            .all(|(i, axis)| offset.dot(axis).abs() <= self.half_extents[i])
+    }
+}
@@ -0,0 +1,273 @@
+use core::array;
+
+use num_traits::Float;
+
+use crate::geometry::{
+    Aabb, Aabb2, Aabb3, Capsule, Circle, Obb, Obb2, Sphere, Triangle, Triangle2,
+};
+use crate::vector::vector_ops::{Vector2Ops, Vector3Ops};
+use crate::vector::{Vector, Vector2, Vector3};
+
+// Whether the projections [min, max] of two shapes onto an axis are disjoint, given the
+// projected center distance and the projected radii
+fn separated<T: Float>(distance: T, radius_a: T, radius_b: T) -> bool {
+    distance.abs() > radius_a + radius_b
+}
+
+// Ball against box, shared by spheres and circles
+fn ball_intersects_aabb<T: Float, const D: usize>(
+    center: Vector<T, D>,
+    radius: T,
+    aabb: &Aabb<T, D>,
+) -> bool {
+    aabb.distance_squared(center) <= radius * radius
+}
+
+impl<T: Float> Sphere<T> {
+    pub fn intersects_sphere(&self, other: &Sphere<T>) -> bool {
+        let reach = self.radius + other.radius;
+        (other.center - self.center).length_squared() <= reach * reach
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb3<T>) -> bool {
+        ball_intersects_aabb(self.center, self.radius, aabb)
+    }
+}
+
+impl<T: Float> Circle<T> {
+    pub fn intersects_circle(&self, other: &Circle<T>) -> bool {
+        let reach = self.radius + other.radius;
+        (other.center - self.center).length_squared() <= reach * reach
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb2<T>) -> bool {
+        ball_intersects_aabb(self.center, self.radius, aabb)
+    }
+}
+
+impl<T: Float, const D: usize> Capsule<T, D> {
+    pub fn intersects_capsule(&self, other: &Self) -> bool {
+        let closest = self.segment().closest_points(&other.segment());
+        let reach = self.radius + other.radius;
+        closest.distance_squared <= reach * reach
+    }
+}
+
+impl<T: Float> Obb<T> {
+    /// Separating axis test over the 15 candidate axes (Gottschalk)
+    pub fn intersects_obb(&self, other: &Obb<T>) -> bool {
+        // Padding on the rotation terms so near-parallel edges, whose cross products
+        // vanish, cannot produce a false separating axis from rounding error
+        let padding = T::epsilon().sqrt();
+        let (ha, hb) = (self.half_extents, other.half_extents);
+        let a = [self.axis(0), self.axis(1), self.axis(2)];
+        let b = [other.axis(0), other.axis(1), other.axis(2)];
+        let r: [[T; 3]; 3] = array::from_fn(|i| array::from_fn(|j| a[i].dot(&b[j])));
+        let abs_r: [[T; 3]; 3] = array::from_fn(|i| array::from_fn(|j| r[i][j].abs() + padding));
+        let offset = other.center - self.center;
+        let t = [offset.dot(&a[0]), offset.dot(&a[1]), offset.dot(&a[2])];
+
+        for i in 0..3 {
+            let rb = hb[0] * abs_r[i][0] + hb[1] * abs_r[i][1] + hb[2] * abs_r[i][2];
+            if separated(t[i], ha[i], rb) {
+                return false;
+            }
+        }
+        for j in 0..3 {
+            let ra = ha[0] * abs_r[0][j] + ha[1] * abs_r[1][j] + ha[2] * abs_r[2][j];
+            let distance = t[0] * r[0][j] + t[1] * r[1][j] + t[2] * r[2][j];
+            if separated(distance, ra, hb[j]) {
+                return false;
+            }
+        }
+        for i in 0..3 {
+            let (i1, i2) = ((i + 1) % 3, (i + 2) % 3);
+            for j in 0..3 {
+                let (j1, j2) = ((j + 1) % 3, (j + 2) % 3);
+                let ra = ha[i1] * abs_r[i2][j] + ha[i2] * abs_r[i1][j];
+                let rb = hb[j1] * abs_r[i][j2] + hb[j2] * abs_r[i][j1];
+                let distance = t[i2] * r[i1][j] - t[i1] * r[i2][j];
+                if separated(distance, ra, rb) {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<T: Float> Obb2<T> {
+    /// Separating axis test over the four box axes
+    pub fn intersects_obb(&self, other: &Obb2<T>) -> bool {
+        let (a, b) = (self.axes(), other.axes());
+        let offset = other.center - self.center;
+        let radius = |axes: &[Vector2<T>; 2], half: Vector2<T>, axis: &Vector2<T>| {
+            half[0] * axes[0].dot(axis).abs() + half[1] * axes[1].dot(axis).abs()
+        };
+        a.iter().chain(b.iter()).all(|axis| {
+            !separated(
+                offset.dot(axis),
+                radius(&a, self.half_extents, axis),
+                radius(&b, other.half_extents, axis),
+            )
+        })
+    }
+}
+
+// Whether a triangle's projection onto `axis` misses the box [-half, half] projection.
+// `project` gives the dot product with the axis, `box_radius` the box's projected radius.
+fn triangle_separated<T: Float, V: Copy>(
+    vertices: [V; 3],
+    project: impl Fn(V) -> T,
+    box_radius: T,
+) -> bool {
+    let p = vertices.map(project);
+    let min = p[0].min(p[1]).min(p[2]);
+    let max = p[0].max(p[1]).max(p[2]);
+    min > box_radius || max < -box_radius
+}
+
+impl<T: Float> Triangle<T> {
+    /// Separating axis test over the 13 candidate axes (Akenine-Möller)
+    pub fn intersects_aabb(&self, aabb: &Aabb3<T>) -> bool {
+        let center = aabb.center();
+        let half = aabb.extents();
+        let v = [self.a - center, self.b - center, self.c - center];
+        let edges = [v[1] - v[0], v[2] - v[1], v[0] - v[2]];
+        let unit = |i: usize| {
+            let mut axis = Vector3::from_array([T::zero(); 3]);
+            axis[i] = T::one();
+            axis
+        };
+
+        let box_axes = (0..3).map(unit);
+        let edge_axes = (0..9).map(|k| edges[k / 3].cross(&unit(k % 3)));
+        let normal = core::iter::once(edges[0].cross(&edges[1]));
+        box_axes.chain(normal).chain(edge_axes).all(|axis| {
+            let box_radius =
+                half[0] * axis[0].abs() + half[1] * axis[1].abs() + half[2] * axis[2].abs();
+            !triangle_separated(v, |p: Vector3<T>| p.dot(&axis), box_radius)
+        })
+    }
+}
+
+impl<T: Float> Triangle2<T> {
+    /// Separating axis test over the box axes and the triangle edge normals
+    pub fn intersects_aabb(&self, aabb: &Aabb2<T>) -> bool {
+        let center = aabb.center();
+        let half = aabb.extents();
+        let v = [self.a - center, self.b - center, self.c - center];
+        let edge_normals = (0..3).map(|k| (v[(k + 1) % 3] - v[k]).perpendicular());
+        let box_axes = [
+            Vector2::new(T::one(), T::zero()),
+            Vector2::new(T::zero(), T::one()),
+        ];
+        box_axes.into_iter().chain(edge_normals).all(|axis| {
+            let box_radius = half[0] * axis[0].abs() + half[1] * axis[1].abs();
+            !triangle_separated(v, |p: Vector2<T>| p.dot(&axis), box_radius)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::{
+        Aabb2f64, Aabb3f64, Capsule, Circle, Obb, Obb2, Sphere, Triangle, Triangle2,
+    };
+    use crate::quaternian::Quaternion;
+    use crate::test_util::v3;
+    use crate::vector::Vector2f64;
+
+    fn v2(x: f64, y: f64) -> Vector2f64 {
+        Vector2f64::new(x, y)
+    }
+
+    #[test]
+    fn test_sphere_and_circle_overlap() {
+        let a = Sphere::new(v3(0.0, 0.0, 0.0), 1.0);
+        assert!(a.intersects_sphere(&Sphere::new(v3(1.5, 0.0, 0.0), 0.5)));
+        assert!(!a.intersects_sphere(&Sphere::new(v3(1.5, 1.5, 0.0), 0.5)));
+
+        let aabb = Aabb3f64::new(v3(1.0, 1.0, 1.0), v3(2.0, 2.0, 2.0));
+        assert!(!a.intersects_aabb(&aabb));
+        assert!(Sphere::new(v3(0.0, 0.0, 0.0), 1.8).intersects_aabb(&aabb));
+
+        let c = Circle::new(v2(0.0, 0.0), 1.0);
+        assert!(c.intersects_circle(&Circle::new(v2(0.0, 1.9), 1.0)));
+        assert!(!c.intersects_circle(&Circle::new(v2(2.0, 1.0), 1.0)));
+        let rect = Aabb2f64::new(v2(0.5, 0.5), v2(3.0, 3.0));
+        assert!(c.intersects_aabb(&rect));
+        assert!(!Circle::new(v2(0.0, 0.0), 0.7).intersects_aabb(&rect));
+    }
+
+    #[test]
+    fn test_obb_overlap() {
+        let half = v3(1.0, 1.0, 1.0);
+        let a = Obb::new(v3(0.0, 0.0, 0.0), half, Quaternion::identity());
+        let turned = Quaternion::from_axis_angle(v3(0.0, 0.0, 1.0), core::f64::consts::FRAC_PI_4);
+
+        // Axis-aligned boxes 2.2 apart are separate, but turning one puts its corner inside
+        let b = Obb::new(v3(2.2, 0.0, 0.0), half, Quaternion::identity());
+        assert!(!a.intersects_obb(&b));
+        let b = Obb::new(v3(2.2, 0.0, 0.0), half, turned);
+        assert!(a.intersects_obb(&b));
+
+        // Crossed edges on top of each other, separated only along an edge-edge axis
+        let eighth = core::f64::consts::FRAC_PI_4;
+        let tilt_x = Quaternion::from_axis_angle(v3(1.0, 0.0, 0.0), eighth);
+        let tilt_y = Quaternion::from_axis_angle(v3(0.0, 1.0, 0.0), eighth);
+        let edge_a = Obb::new(v3(0.0, 0.0, 0.0), half, tilt_x);
+        let gap = 2.0 * 2.0f64.sqrt();
+        assert!(!edge_a.intersects_obb(&Obb::new(v3(0.0, 0.0, gap + 0.01), half, tilt_y)));
+        assert!(edge_a.intersects_obb(&Obb::new(v3(0.0, 0.0, gap - 0.01), half, tilt_y)));
+
+        let square = Obb2::new(v2(0.0, 0.0), v2(1.0, 1.0), 0.0);
+        assert!(!square.intersects_obb(&Obb2::new(v2(2.2, 0.0), v2(1.0, 1.0), 0.0)));
+        assert!(square.intersects_obb(&Obb2::new(
+            v2(2.2, 0.0),
+            v2(1.0, 1.0),
+            core::f64::consts::FRAC_PI_4
+        )));
+        assert!(!square.intersects_obb(&Obb2::new(
+            v2(2.0, 2.0),
+            v2(1.0, 0.2),
+            core::f64::consts::FRAC_PI_4
+        )));
+    }
+
+    #[test]
+    fn test_triangle_aabb_overlap() {
+        let aabb = Aabb3f64::new(v3(-1.0, -1.0, -1.0), v3(1.0, 1.0, 1.0));
+        // Large triangle slicing through the box with all vertices outside
+        let through = Triangle::new(v3(-5.0, -5.0, 0.0), v3(5.0, -5.0, 0.0), v3(0.0, 5.0, 0.0));
+        assert!(through.intersects_aabb(&aabb));
+        // Plane of the triangle misses the box
+        let above = Triangle::new(v3(-5.0, -5.0, 2.0), v3(5.0, -5.0, 2.0), v3(0.0, 5.0, 2.0));
+        assert!(!above.intersects_aabb(&aabb));
+        // Near a corner, separated only by an edge axis
+        let corner = Triangle::new(v3(1.5, 0.0, 1.5), v3(0.0, 1.5, 1.5), v3(1.5, 1.5, 0.0));
+        assert!(!corner.intersects_aabb(&Aabb3f64::new(v3(-1.0, -1.0, -1.0), v3(0.9, 0.9, 0.9))));
+
+        let rect = Aabb2f64::new(v2(0.0, 0.0), v2(1.0, 1.0));
+        assert!(Triangle2::new(v2(-1.0, 0.5), v2(2.0, 0.5), v2(0.5, 3.0)).intersects_aabb(&rect));
+        // Hypotenuse passes just beyond the box corner
+        let diagonal = Triangle2::new(v2(2.1, 0.0), v2(3.0, 3.0), v2(0.0, 2.1));
+        assert!(!diagonal.intersects_aabb(&rect));
+    }
+
+    #[test]
+    fn test_capsule_overlap() {
+        let a = Capsule::new(v3(0.0, 0.0, 0.0), v3(0.0, 4.0, 0.0), 0.5);
+        let crossing = Capsule::new(v3(-2.0, 2.0, 0.9), v3(2.0, 2.0, 0.9), 0.5);
+        assert!(a.intersects_capsule(&crossing));
+        let beyond = Capsule::new(v3(-2.0, 2.0, 1.1), v3(2.0, 2.0, 1.1), 0.5);
+        assert!(!a.intersects_capsule(&beyond));
+        // Parallel and end to end
+        let above = Capsule::new(v3(0.0, 4.9, 0.0), v3(0.0, 8.0, 0.0), 0.5);
+        assert!(a.intersects_capsule(&above));
+
+        let flat = Capsule::new(v2(0.0, 0.0), v2(4.0, 0.0), 1.0);
+        assert!(flat.intersects_capsule(&Capsule::new(v2(2.0, 1.5), v2(2.0, 5.0), 0.6)));
+        assert!(!flat.intersects_capsule(&Capsule::new(v2(5.5, 0.0), v2(7.0, 0.0), 0.4)));
+    }
+}
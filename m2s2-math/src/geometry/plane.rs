@@ -0,0 +1,44 @@
+use num_traits::Float;
+
+use crate::vector::Vector3;
+use crate::vector::vector_ops::Vector3Ops;
+
+/// Plane `normal . p + d = 0`. Points with positive signed distance are on the side the
+/// normal points to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane<T> {
+    pub normal: Vector3<T>,
+    pub d: T,
+}
+
+impl<T: Float> Plane<T> {
+    pub fn new(normal: Vector3<T>, d: T) -> Self {
+        Plane { normal, d }
+    }
+
+    /// Plane through `point` with the given normal
+    pub fn from_point_normal(point: Vector3<T>, normal: Vector3<T>) -> Self {
+        Plane {
+            normal,
+            d: -normal.dot(&point),
+        }
+    }
+
+    /// Scale so the normal has unit length. A plane with a zero normal is returned as is.
+    pub fn normalize(&self) -> Self {
+        let length = self.normal.length();
+        if length > T::zero() {
+            Plane {
+                normal: self.normal * (T::one() / length),
+                d: self.d / length,
+            }
+        } else {
+            *self
+        }
+    }
+
+    /// Signed distance from the plane, exact only when the normal is unit length
+    pub fn signed_distance(&self, point: Vector3<T>) -> T {
+        self.normal.dot(&point) + self.d
+    }
+}
@@ -0,0 +1,320 @@
+use num_traits::Float;
+
+use crate::geometry::{Aabb3, Obb, Plane, Sphere, Triangle};
+use crate::vector::vector_ops::Vector3Ops;
+use crate::vector::{Vector, Vector3};
+
+/// Half-line `origin + t * direction` for `t >= 0`.
+///
+/// The direction does not need to be unit length; hit distances are measured in multiples
+/// of it, so they are true distances only for unit directions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray<T, const D: usize> {
+    pub origin: Vector<T, D>,
+    pub direction: Vector<T, D>,
+}
+
+pub type Ray2<T> = Ray<T, 2>;
+pub type Ray3<T> = Ray<T, 3>;
+
+pub type Ray2f32 = Ray2<f32>;
+pub type Ray2f64 = Ray2<f64>;
+pub type Ray3f32 = Ray3<f32>;
+pub type Ray3f64 = Ray3<f64>;
+
+/// Closest intersection of a ray with a shape.
+///
+/// `normal` is the unit outward surface normal. For planes and triangles, which have no
+/// inside, it faces the ray origin. When the ray starts inside a closed shape the hit is
+/// where it leaves.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RayHit<T> {
+    pub distance: T,
+    pub point: Vector3<T>,
+    pub normal: Vector3<T>,
+}
+
+/// Ray-triangle hit with the barycentric weights of the triangle's `a`, `b` and `c`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TriangleHit<T> {
+    pub hit: RayHit<T>,
+    pub barycentric: Vector3<T>,
+}
+
+impl<T: Float, const D: usize> Ray<T, D> {
+    pub fn new(origin: Vector<T, D>, direction: Vector<T, D>) -> Self {
+        Ray { origin, direction }
+    }
+
+    /// Point at parameter `t`
+    pub fn at(&self, t: T) -> Vector<T, D> {
+        self.origin + self.direction * t
+    }
+}
+
+impl<T: Float> Ray3<T> {
+    fn hit(&self, distance: T, normal: Vector3<T>) -> RayHit<T> {
+        RayHit {
+            distance,
+            point: self.at(distance),
+            normal,
+        }
+    }
+
+    pub fn intersect_sphere(&self, sphere: &Sphere<T>) -> Option<RayHit<T>> {
+        let offset = self.origin - sphere.center;
+        let a = self.direction.length_squared();
+        let half_b = offset.dot(&self.direction);
+        let c = offset.length_squared() - sphere.radius * sphere.radius;
+        let discriminant = half_b * half_b - a * c;
+        if discriminant < T::zero() || a == T::zero() {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        let near = (-half_b - root) / a;
+        let far = (-half_b + root) / a;
+        let distance = if near >= T::zero() { near } else { far };
+        if distance < T::zero() {
+            return None;
+        }
+        let normal = (self.at(distance) - sphere.center) * (T::one() / sphere.radius);
+        Some(self.hit(distance, normal))
+    }
+
+    /// Hit with an infinite plane, `None` when the ray is parallel to it or points away
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<RayHit<T>> {
+        let denominator = plane.normal.dot(&self.direction);
+        if denominator == T::zero() {
+            return None;
+        }
+        let distance = -plane.signed_distance(self.origin) / denominator;
+        if distance < T::zero() {
+            return None;
+        }
+        let normal = plane.normal.normalize();
+        let normal = if denominator < T::zero() {
+            normal
+        } else {
+            -normal
+        };
+        Some(self.hit(distance, normal))
+    }
+
+    /// Slab test against an axis-aligned box
+    pub fn intersect_aabb(&self, aabb: &Aabb3<T>) -> Option<RayHit<T>> {
+        let mut enter = (T::neg_infinity(), 0);
+        let mut exit = (T::infinity(), 0);
+        for i in 0..3 {
+            let (origin, direction) = (self.origin[i], self.direction[i]);
+            if direction == T::zero() {
+                if origin < aabb.min[i] || origin > aabb.max[i] {
+                    return None;
+                }
+                continue;
+            }
+            let t0 = (aabb.min[i] - origin) / direction;
+            let t1 = (aabb.max[i] - origin) / direction;
+            let (near, far) = if t0 <= t1 { (t0, t1) } else { (t1, t0) };
+            if near > enter.0 {
+                enter = (near, i);
+            }
+            if far < exit.0 {
+                exit = (far, i);
+            }
+        }
+        if exit.0 < enter.0 || exit.0 < T::zero() {
+            return None;
+        }
+
+        // Entering faces point against the ray, exiting faces along it
+        let face_normal = |axis: usize, along: bool| {
+            let mut normal = Vector3::from_array([T::zero(); 3]);
+            let positive = (self.direction[axis] > T::zero()) == along;
+            normal[axis] = if positive { T::one() } else { -T::one() };
+            normal
+        };
+        if enter.0 >= T::zero() {
+            Some(self.hit(enter.0, face_normal(enter.1, false)))
+        } else {
+            Some(self.hit(exit.0, face_normal(exit.1, true)))
+        }
+    }
+
+    /// Two-sided Möller-Trumbore intersection
+    pub fn intersect_triangle(&self, triangle: &Triangle<T>) -> Option<TriangleHit<T>> {
+        let edge1 = triangle.b - triangle.a;
+        let edge2 = triangle.c - triangle.a;
+        let p = self.direction.cross(&edge2);
+        let determinant = edge1.dot(&p);
+        // Relative test: the determinant is |direction| * |e1 x e2| * cos(angle to normal)
+        let face = edge1.cross(&edge2);
+        let tolerance = T::epsilon() * face.length() * self.direction.length();
+        if determinant.abs() <= tolerance {
+            return None;
+        }
+
+        let inverse = T::one() / determinant;
+        let s = self.origin - triangle.a;
+        let u = s.dot(&p) * inverse;
+        if u < T::zero() || u > T::one() {
+            return None;
+        }
+        let q = s.cross(&edge1);
+        let v = self.direction.dot(&q) * inverse;
+        if v < T::zero() || u + v > T::one() {
+            return None;
+        }
+        let distance = edge2.dot(&q) * inverse;
+        if distance < T::zero() {
+            return None;
+        }
+
+        let normal = face.normalize();
+        let normal = if normal.dot(&self.direction) > T::zero() {
+            -normal
+        } else {
+            normal
+        };
+        Some(TriangleHit {
+            hit: self.hit(distance, normal),
+            barycentric: Vector3::new(T::one() - u - v, u, v),
+        })
+    }
+
+    /// Slab test in the box's local frame
+    pub fn intersect_obb(&self, obb: &Obb<T>) -> Option<RayHit<T>> {
+        let inverse = obb.rotation.conjugate();
+        let local = Ray3::new(
+            inverse.rotate_vector(self.origin - obb.center),
+            inverse.rotate_vector(self.direction),
+        );
+        let bounds = Aabb3::new(-obb.half_extents, obb.half_extents);
+        let local_hit = local.intersect_aabb(&bounds)?;
+        Some(self.hit(
+            local_hit.distance,
+            obb.rotation.rotate_vector(local_hit.normal),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ray3f64;
+    use crate::geometry::{Aabb3f64, Obb, Plane, Sphere, Triangle};
+    use crate::quaternian::Quaternion;
+    use crate::test_util::{assert_vec_near, v3};
+    use crate::vector::vector_ops::Vector3Ops;
+
+    #[test]
+    fn test_ray_sphere() {
+        let sphere = Sphere::new(v3(0.0, 0.0, -5.0), 1.0);
+        let ray = Ray3f64::new(v3(0.0, 0.0, 0.0), v3(0.0, 0.0, -2.0));
+        let hit = ray.intersect_sphere(&sphere).unwrap();
+        assert!((hit.distance - 2.0).abs() < 1e-12);
+        assert_vec_near(hit.point, v3(0.0, 0.0, -4.0), 1e-12);
+        assert_vec_near(hit.normal, v3(0.0, 0.0, 1.0), 1e-12);
+
+        // From inside the hit is on the way out
+        let inside = Ray3f64::new(v3(0.0, 0.0, -5.0), v3(1.0, 0.0, 0.0));
+        let hit = inside.intersect_sphere(&sphere).unwrap();
+        assert_vec_near(hit.point, v3(1.0, 0.0, -5.0), 1e-12);
+        assert_vec_near(hit.normal, v3(1.0, 0.0, 0.0), 1e-12);
+
+        let behind = Ray3f64::new(v3(0.0, 0.0, 0.0), v3(0.0, 0.0, 1.0));
+        assert!(behind.intersect_sphere(&sphere).is_none());
+        let miss = Ray3f64::new(v3(0.0, 2.0, 0.0), v3(0.0, 0.0, -1.0));
+        assert!(miss.intersect_sphere(&sphere).is_none());
+    }
+
+    #[test]
+    fn test_ray_plane() {
+        let ground = Plane::from_point_normal(v3(0.0, -1.0, 0.0), v3(0.0, 1.0, 0.0));
+        let ray = Ray3f64::new(v3(0.0, 4.0, 0.0), v3(1.0, -1.0, 0.0));
+        let hit = ray.intersect_plane(&ground).unwrap();
+        assert!((hit.distance - 5.0).abs() < 1e-12);
+        assert_vec_near(hit.point, v3(5.0, -1.0, 0.0), 1e-12);
+        assert_vec_near(hit.normal, v3(0.0, 1.0, 0.0), 1e-12);
+
+        // From below, the normal faces the ray
+        let up = Ray3f64::new(v3(0.0, -3.0, 0.0), v3(0.0, 1.0, 0.0));
+        assert_vec_near(
+            up.intersect_plane(&ground).unwrap().normal,
+            v3(0.0, -1.0, 0.0),
+            1e-12,
+        );
+
+        let parallel = Ray3f64::new(v3(0.0, 4.0, 0.0), v3(1.0, 0.0, 0.0));
+        assert!(parallel.intersect_plane(&ground).is_none());
+        let away = Ray3f64::new(v3(0.0, 4.0, 0.0), v3(0.0, 1.0, 0.0));
+        assert!(away.intersect_plane(&ground).is_none());
+    }
+
+    #[test]
+    fn test_ray_aabb() {
+        let aabb = Aabb3f64::new(v3(-1.0, -1.0, -1.0), v3(1.0, 1.0, 1.0));
+        let ray = Ray3f64::new(v3(-5.0, 0.5, 0.0), v3(1.0, 0.0, 0.0));
+        let hit = ray.intersect_aabb(&aabb).unwrap();
+        assert!((hit.distance - 4.0).abs() < 1e-12);
+        assert_vec_near(hit.normal, v3(-1.0, 0.0, 0.0), 1e-12);
+
+        let slanted = Ray3f64::new(v3(0.5, 0.5, 3.0), v3(0.1, 0.0, -1.0));
+        let hit = slanted.intersect_aabb(&aabb).unwrap();
+        assert_vec_near(hit.point, v3(0.7, 0.5, 1.0), 1e-12);
+        assert_vec_near(hit.normal, v3(0.0, 0.0, 1.0), 1e-12);
+
+        let inside = Ray3f64::new(v3(0.0, 0.0, 0.0), v3(0.0, -1.0, 0.0));
+        let hit = inside.intersect_aabb(&aabb).unwrap();
+        assert_vec_near(hit.point, v3(0.0, -1.0, 0.0), 1e-12);
+        assert_vec_near(hit.normal, v3(0.0, -1.0, 0.0), 1e-12);
+
+        let miss = Ray3f64::new(v3(-5.0, 2.0, 0.0), v3(1.0, 0.0, 0.0));
+        assert!(miss.intersect_aabb(&aabb).is_none());
+    }
+
+    #[test]
+    fn test_ray_triangle() {
+        let triangle = Triangle::new(v3(0.0, 0.0, 0.0), v3(2.0, 0.0, 0.0), v3(0.0, 2.0, 0.0));
+        let ray = Ray3f64::new(v3(0.5, 0.5, 3.0), v3(0.0, 0.0, -1.0));
+        let hit = ray.intersect_triangle(&triangle).unwrap();
+        assert!((hit.hit.distance - 3.0).abs() < 1e-12);
+        assert_vec_near(hit.hit.normal, v3(0.0, 0.0, 1.0), 1e-12);
+        assert_vec_near(hit.barycentric, v3(0.5, 0.25, 0.25), 1e-12);
+        assert_vec_near(
+            triangle.from_barycentric(hit.barycentric),
+            hit.hit.point,
+            1e-12,
+        );
+
+        // Back faces are hit too, with the normal facing the ray
+        let back = Ray3f64::new(v3(0.5, 0.5, -3.0), v3(0.0, 0.0, 1.0));
+        assert_vec_near(
+            back.intersect_triangle(&triangle).unwrap().hit.normal,
+            v3(0.0, 0.0, -1.0),
+            1e-12,
+        );
+
+        let outside = Ray3f64::new(v3(1.5, 1.5, 3.0), v3(0.0, 0.0, -1.0));
+        assert!(outside.intersect_triangle(&triangle).is_none());
+        let parallel = Ray3f64::new(v3(0.5, 0.5, 0.0), v3(1.0, 0.0, 0.0));
+        assert!(parallel.intersect_triangle(&triangle).is_none());
+    }
+
+    #[test]
+    fn test_ray_obb() {
+        let rotation = Quaternion::from_axis_angle(v3(0.0, 0.0, 1.0), core::f64::consts::FRAC_PI_4);
+        let obb = Obb::new(v3(10.0, 0.0, 0.0), v3(1.0, 1.0, 1.0), rotation);
+        let ray = Ray3f64::new(v3(0.0, 0.0, 0.0), v3(1.0, 0.0, 0.0));
+        let hit = ray.intersect_obb(&obb).unwrap();
+
+        // The box is turned 45 degrees, so a corner points at the ray
+        assert!((hit.distance - (10.0 - 2.0f64.sqrt())).abs() < 1e-12);
+        assert!((hit.normal.length() - 1.0).abs() < 1e-12);
+        assert!(hit.normal[0] < 0.0);
+
+        let miss = Ray3f64::new(v3(0.0, 1.5, 0.0), v3(1.0, 0.0, 0.0));
+        assert!(miss.intersect_obb(&obb).is_none());
+        assert!(obb.contains_point(v3(10.0, 1.2, 0.0)));
+        assert!(!obb.contains_point(v3(11.5, 0.0, 0.0)));
+    }
+}
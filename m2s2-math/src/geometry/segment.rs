@@ -0,0 +1,29 @@
+use num_traits::Float;
+
+use crate::vector::Vector;
+
+/// Line segment from `a` to `b`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment<T, const D: usize> {
+    pub a: Vector<T, D>,
+    pub b: Vector<T, D>,
+}
+
+pub type Segment2<T> = Segment<T, 2>;
+pub type Segment3<T> = Segment<T, 3>;
+
+impl<T: Float, const D: usize> Segment<T, D> {
+    pub fn new(a: Vector<T, D>, b: Vector<T, D>) -> Self {
+        Segment { a, b }
+    }
+
+    /// Point at parameter `t`, `a` at 0 and `b` at 1
+    pub fn at(&self, t: T) -> Vector<T, D> {
+        self.a + (self.b - self.a) * t
+    }
+
+    pub fn length(&self) -> T {
+        let d = self.b - self.a;
+        super::dot(&d, &d).sqrt()
+    }
+}
@@ -0,0 +1,43 @@
+use num_traits::Float;
+
+use crate::geometry::Aabb3;
+use crate::vector::vector_ops::{Vector2Ops, Vector3Ops};
+use crate::vector::{Vector2, Vector3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere<T> {
+    pub center: Vector3<T>,
+    pub radius: T,
+}
+
+impl<T: Float> Sphere<T> {
+    pub fn new(center: Vector3<T>, radius: T) -> Self {
+        Sphere { center, radius }
+    }
+
+    pub fn contains_point(&self, point: Vector3<T>) -> bool {
+        (point - self.center).length_squared() <= self.radius * self.radius
+    }
+
+    pub fn bounds(&self) -> Aabb3<T> {
+        let radius = Vector3::new(self.radius, self.radius, self.radius);
+        Aabb3::from_center_half_extents(self.center, radius)
+    }
+}
+
+/// 2D counterpart of `Sphere`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle<T> {
+    pub center: Vector2<T>,
+    pub radius: T,
+}
+
+impl<T: Float> Circle<T> {
+    pub fn new(center: Vector2<T>, radius: T) -> Self {
+        Circle { center, radius }
+    }
+
+    pub fn contains_point(&self, point: Vector2<T>) -> bool {
+        (point - self.center).length_squared() <= self.radius * self.radius
+    }
+}
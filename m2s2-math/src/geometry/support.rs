@@ -0,0 +1,107 @@
+use num_traits::Float;
+
+use crate::geometry::{Aabb3, Capsule3, Obb, Sphere, Triangle};
+use crate::vector::Vector3;
+use crate::vector::vector_ops::Vector3Ops;
+
+/// Convex shape described by its support function, the input to GJK and EPA
+pub trait SupportMap<T: Float> {
+    /// Point of the shape furthest along `direction`. The direction need not be unit length
+    /// and may be zero, in which case any point of the shape is valid.
+    fn support(&self, direction: Vector3<T>) -> Vector3<T>;
+}
+
+/// Convex hull of a set of points, borrowed so no allocation is needed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvexHull<'a, T> {
+    points: &'a [Vector3<T>],
+}
+
+impl<'a, T: Float> ConvexHull<'a, T> {
+    /// Hull of `points`, which must not be empty
+    pub fn new(points: &'a [Vector3<T>]) -> Self {
+        assert!(!points.is_empty(), "ConvexHull needs at least one point");
+        ConvexHull { points }
+    }
+
+    /// Points the hull was built from
+    pub fn points(&self) -> &'a [Vector3<T>] {
+        self.points
+    }
+}
+
+// Unit vector along `direction`, or the zero vector when it has no length
+pub(crate) fn unit_or_zero<T: Float>(direction: Vector3<T>) -> Vector3<T> {
+    let length = direction.length();
+    if length > T::zero() {
+        direction * (T::one() / length)
+    } else {
+        direction
+    }
+}
+
+// Corner of a box of `half_extents` about the origin furthest along `direction`
+fn box_corner<T: Float>(half_extents: Vector3<T>, direction: Vector3<T>) -> Vector3<T> {
+    let pick = |i: usize| {
+        if direction[i] >= T::zero() {
+            half_extents[i]
+        } else {
+            -half_extents[i]
+        }
+    };
+    Vector3::new(pick(0), pick(1), pick(2))
+}
+
+impl<T: Float> SupportMap<T> for Sphere<T> {
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        self.center + unit_or_zero(direction) * self.radius
+    }
+}
+
+impl<T: Float> SupportMap<T> for Aabb3<T> {
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        self.center() + box_corner(self.extents(), direction)
+    }
+}
+
+impl<T: Float> SupportMap<T> for Obb<T> {
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let local = self.rotation.conjugate().rotate_vector(direction);
+        self.center
+            + self
+                .rotation
+                .rotate_vector(box_corner(self.half_extents, local))
+    }
+}
+
+impl<T: Float> SupportMap<T> for Capsule3<T> {
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let end = if self.a.dot(&direction) >= self.b.dot(&direction) {
+            self.a
+        } else {
+            self.b
+        };
+        end + unit_or_zero(direction) * self.radius
+    }
+}
+
+impl<T: Float> SupportMap<T> for Triangle<T> {
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        ConvexHull::new(&[self.a, self.b, self.c]).support(direction)
+    }
+}
+
+impl<T: Float> SupportMap<T> for ConvexHull<'_, T> {
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        let mut best = self.points[0];
+        let mut best_dot = best.dot(&direction);
+        for point in &self.points[1..] {
+            let d = point.dot(&direction);
+            if d > best_dot {
+                best = *point;
+                best_dot = d;
+            }
+        }
+        best
+    }
+}
@@ -0,0 +1,597 @@
+use num_traits::Float;
+
+use crate::geometry::support::unit_or_zero;
+use crate::geometry::{
+    Aabb3, Plane, Ray3, Sphere, SupportMap, Triangle, epa_penetration, gjk_distance,
+};
+use crate::vector::Vector3;
+use crate::vector::vector_ops::Vector3Ops;
+
+const MAX_ADVANCEMENT_STEPS: usize = 64;
+
+/// First contact of a shape moving by a displacement over one step.
+///
+/// `time` is the fraction of the displacement in `[0, 1]` at which the shapes first touch.
+/// `normal` is the unit contact normal pointing from the obstacle towards the moving shape
+/// and `point` the contact point at that time. Shapes that already overlap report time
+/// zero, with the normal pointing the way that separates them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeOfImpact<T> {
+    pub time: T,
+    pub normal: Vector3<T>,
+    pub point: Vector3<T>,
+}
+
+// Earliest of some hits given as (time, normal)
+fn earliest<T: Float>(
+    hits: impl IntoIterator<Item = Option<(T, Vector3<T>)>>,
+) -> Option<(T, Vector3<T>)> {
+    hits.into_iter()
+        .flatten()
+        .min_by(|x, y| x.0.partial_cmp(&y.0).unwrap())
+}
+
+// Hit of a ray with the capsule of `radius` around segment ab (Ericson 5.3.7 for the side,
+// sphere tests for the caps)
+fn ray_capsule<T: Float>(
+    ray: &Ray3<T>,
+    a: Vector3<T>,
+    b: Vector3<T>,
+    radius: T,
+) -> Option<(T, Vector3<T>)> {
+    let (d, m, n) = (b - a, ray.origin - a, ray.direction);
+    let (md, nd, dd) = (m.dot(&d), n.dot(&d), d.dot(&d));
+    let qa = dd * n.length_squared() - nd * nd;
+    let qb = dd * m.dot(&n) - nd * md;
+    let qc = dd * (m.length_squared() - radius * radius) - md * md;
+    let discriminant = qb * qb - qa * qc;
+
+    // Rays along the axis can only enter through the caps
+    let side = if qa > T::zero() && discriminant >= T::zero() {
+        let t = (-qb - discriminant.sqrt()) / qa;
+        let along = md + t * nd;
+        (t >= T::zero() && along >= T::zero() && along <= dd).then(|| {
+            let point = ray.at(t);
+            let axis = a + d * (along / dd);
+            (t, (point - axis) * (T::one() / radius))
+        })
+    } else {
+        None
+    };
+    let cap = |center| {
+        ray.intersect_sphere(&Sphere::new(center, radius))
+            .map(|hit| (hit.distance, hit.normal))
+    };
+    earliest([side, cap(a), cap(b)])
+}
+
+impl<T: Float> Sphere<T> {
+    fn impact(&self, velocity: Vector3<T>, (time, normal): (T, Vector3<T>)) -> TimeOfImpact<T> {
+        TimeOfImpact {
+            time,
+            normal,
+            point: self.center + velocity * time - normal * self.radius,
+        }
+    }
+
+    // Contact for a sphere already touching a shape whose closest point to the center is
+    // `closest`
+    fn resting(&self, velocity: Vector3<T>, closest: Vector3<T>) -> TimeOfImpact<T> {
+        let offset = self.center - closest;
+        let normal = if offset.length_squared() > T::zero() {
+            offset.normalize()
+        } else {
+            unit_or_zero(-velocity)
+        };
+        TimeOfImpact {
+            time: T::zero(),
+            normal,
+            point: closest,
+        }
+    }
+
+    /// First contact of the sphere moving by `velocity` with a two-sided plane
+    pub fn sweep_plane(&self, velocity: Vector3<T>, plane: &Plane<T>) -> Option<TimeOfImpact<T>> {
+        let plane = plane.normalize();
+        let distance = plane.signed_distance(self.center);
+        let normal = if distance >= T::zero() {
+            plane.normal
+        } else {
+            -plane.normal
+        };
+        let height = distance.abs();
+        if height <= self.radius {
+            return Some(self.resting(velocity, self.center - normal * height));
+        }
+
+        let approach = -normal.dot(&velocity);
+        if approach <= T::zero() {
+            return None;
+        }
+        let time = (height - self.radius) / approach;
+        (time <= T::one()).then(|| self.impact(velocity, (time, normal)))
+    }
+
+    /// First contact of the sphere moving by `velocity` with a triangle, either face
+    pub fn sweep_triangle(
+        &self,
+        velocity: Vector3<T>,
+        triangle: &Triangle<T>,
+    ) -> Option<TimeOfImpact<T>> {
+        let closest = triangle.closest_point(self.center);
+        if closest.distance_squared <= self.radius * self.radius {
+            return Some(self.resting(velocity, closest.point));
+        }
+
+        // The triangle grown by the radius: the face pushed out towards the sphere and a
+        // capsule around each edge
+        let ray = Ray3::new(self.center, velocity);
+        let face = (triangle.b - triangle.a).cross(&(triangle.c - triangle.a));
+        let face_hit = (face.length_squared() > T::zero()).then(|| {
+            let normal = face.normalize();
+            let normal = if normal.dot(&(self.center - triangle.a)) >= T::zero() {
+                normal
+            } else {
+                -normal
+            };
+            let offset = normal * self.radius;
+            let lifted = Triangle::new(
+                triangle.a + offset,
+                triangle.b + offset,
+                triangle.c + offset,
+            );
+            ray.intersect_triangle(&lifted)
+                .map(|hit| (hit.hit.distance, normal))
+        });
+        let edges = [
+            (triangle.a, triangle.b),
+            (triangle.b, triangle.c),
+            (triangle.c, triangle.a),
+        ];
+        let hits = edges
+            .into_iter()
+            .map(|(a, b)| ray_capsule(&ray, a, b, self.radius))
+            .chain([face_hit.flatten()]);
+        earliest(hits)
+            .filter(|&(time, _)| time <= T::one())
+            .map(|hit| self.impact(velocity, hit))
+    }
+
+    /// First contact of the sphere moving by `velocity` with an axis-aligned box
+    pub fn sweep_aabb(&self, velocity: Vector3<T>, aabb: &Aabb3<T>) -> Option<TimeOfImpact<T>> {
+        if aabb.distance_squared(self.center) <= self.radius * self.radius {
+            return Some(self.resting(velocity, aabb.closest_point(self.center)));
+        }
+
+        // The box grown by the radius: the box pushed out along each axis and a capsule
+        // around each edge
+        let ray = Ray3::new(self.center, velocity);
+        let slabs = (0..3).map(|axis| {
+            let mut grown = *aabb;
+            grown.min[axis] = grown.min[axis] - self.radius;
+            grown.max[axis] = grown.max[axis] + self.radius;
+            ray.intersect_aabb(&grown)
+                .map(|hit| (hit.distance, hit.normal))
+        });
+        let corner = |bits: usize| {
+            Vector3::new(
+                if bits & 1 == 0 {
+                    aabb.min[0]
+                } else {
+                    aabb.max[0]
+                },
+                if bits & 2 == 0 {
+                    aabb.min[1]
+                } else {
+                    aabb.max[1]
+                },
+                if bits & 4 == 0 {
+                    aabb.min[2]
+                } else {
+                    aabb.max[2]
+                },
+            )
+        };
+        let edges = (0..3).flat_map(|axis| {
+            (0..8)
+                .filter(move |bits| bits & (1 << axis) == 0)
+                .map(move |bits| (bits, bits | (1 << axis)))
+        });
+        let capsules =
+            edges.map(|(from, to)| ray_capsule(&ray, corner(from), corner(to), self.radius));
+        earliest(slabs.chain(capsules))
+            .filter(|&(time, _)| time <= T::one())
+            .map(|hit| self.impact(velocity, hit))
+    }
+}
+
+impl<T: Float> Aabb3<T> {
+    // Point midway across the region where this box and `other` overlap or touch
+    fn contact_point(&self, other: &Self) -> Vector3<T> {
+        let half = T::one() / (T::one() + T::one());
+        Vector3::from_array(core::array::from_fn(|i| {
+            (self.min[i].max(other.min[i]) + self.max[i].min(other.max[i])) * half
+        }))
+    }
+
+    /// First contact of this box moving by `velocity` with a stationary box. For two moving
+    /// boxes pass the difference of their velocities.
+    pub fn sweep_aabb(&self, velocity: Vector3<T>, other: &Self) -> Option<TimeOfImpact<T>> {
+        let unit = |axis: usize, sign: T| {
+            let mut normal = Vector3::from_array([T::zero(); 3]);
+            normal[axis] = sign;
+            normal
+        };
+
+        if self.intersects(other) {
+            // Push out along the axis of least penetration
+            let (_, normal) = (0..3)
+                .flat_map(|i| {
+                    [
+                        (other.max[i] - self.min[i], unit(i, T::one())),
+                        (self.max[i] - other.min[i], unit(i, -T::one())),
+                    ]
+                })
+                .min_by(|x, y| x.0.partial_cmp(&y.0).unwrap())
+                .unwrap();
+            return Some(TimeOfImpact {
+                time: T::zero(),
+                normal,
+                point: self.contact_point(other),
+            });
+        }
+
+        // Ericson 5.5.8: intersect the intervals of time the boxes overlap on each axis
+        let mut enter = (T::zero(), unit(0, T::one()));
+        let mut exit = T::one();
+        for i in 0..3 {
+            let v = velocity[i];
+            if v < T::zero() {
+                if self.max[i] < other.min[i] {
+                    return None;
+                }
+                if other.max[i] < self.min[i] {
+                    let time = (other.max[i] - self.min[i]) / v;
+                    if time > enter.0 {
+                        enter = (time, unit(i, T::one()));
+                    }
+                }
+                exit = exit.min((other.min[i] - self.max[i]) / v);
+            } else if v > T::zero() {
+                if self.min[i] > other.max[i] {
+                    return None;
+                }
+                if self.max[i] < other.min[i] {
+                    let time = (other.min[i] - self.max[i]) / v;
+                    if time > enter.0 {
+                        enter = (time, unit(i, -T::one()));
+                    }
+                }
+                exit = exit.min((other.max[i] - self.min[i]) / v);
+            } else if self.max[i] < other.min[i] || other.max[i] < self.min[i] {
+                return None;
+            }
+            if enter.0 > exit {
+                return None;
+            }
+        }
+
+        let (time, normal) = enter;
+        let offset = velocity * time;
+        let moved = Aabb3::new(self.min + offset, self.max + offset);
+        Some(TimeOfImpact {
+            time,
+            normal,
+            point: moved.contact_point(other),
+        })
+    }
+}
+
+// Shape moved by a fixed offset
+struct Translated<'a, T, S> {
+    shape: &'a S,
+    offset: Vector3<T>,
+}
+
+impl<T: Float, S: SupportMap<T>> SupportMap<T> for Translated<'_, T, S> {
+    fn support(&self, direction: Vector3<T>) -> Vector3<T> {
+        self.shape.support(direction) + self.offset
+    }
+}
+
+/// First contact of two convex shapes moving by `velocity_a` and `velocity_b` over one step,
+/// found by conservative advancement: step forward by the current distance over the closing
+/// speed until the shapes touch. The shapes translate without rotating. The normal points
+/// from `b` towards `a` and the point lies on `b`.
+pub fn time_of_impact<T: Float>(
+    a: &impl SupportMap<T>,
+    velocity_a: Vector3<T>,
+    b: &impl SupportMap<T>,
+    velocity_b: Vector3<T>,
+) -> Option<TimeOfImpact<T>> {
+    let relative = velocity_a - velocity_b;
+    let speed = relative.length();
+    let closest_at = |time: T| {
+        let moved_a = Translated {
+            shape: a,
+            offset: velocity_a * time,
+        };
+        let moved_b = Translated {
+            shape: b,
+            offset: velocity_b * time,
+        };
+        gjk_distance(&moved_a, &moved_b)
+    };
+    let mut time = T::zero();
+    let mut apart = T::zero();
+    let mut last_distance = T::infinity();
+    let mut normal = unit_or_zero(-relative);
+    let mut point = None;
+    let mut tolerance = None;
+
+    for _ in 0..MAX_ADVANCEMENT_STEPS {
+        let Some(closest) = closest_at(time) else {
+            if time == T::zero() {
+                if let Some(penetration) = epa_penetration(a, b) {
+                    normal = -penetration.normal;
+                    point = Some(penetration.second);
+                }
+            } else if let Some(tolerance) = tolerance {
+                // A step taken along a noisy normal went past the contact: bisect back
+                // between the last time the shapes were apart and the overlap
+                let mut overlap = time;
+                time = apart;
+                for _ in 0..MAX_ADVANCEMENT_STEPS {
+                    if (overlap - time) * speed <= tolerance {
+                        break;
+                    }
+                    let middle = (time + overlap) / (T::one() + T::one());
+                    match closest_at(middle) {
+                        Some(closest) => {
+                            time = middle;
+                            normal = (closest.first - closest.second)
+                                * (T::one() / closest.distance_squared.sqrt());
+                            point = Some(closest.second);
+                        }
+                        None => overlap = middle,
+                    }
+                }
+            }
+            break;
+        };
+
+        apart = time;
+        let distance = closest.distance_squared.sqrt();
+        normal = (closest.first - closest.second) * (T::one() / distance);
+        point = Some(closest.second);
+        let tolerance = *tolerance.get_or_insert_with(|| T::epsilon().sqrt() * distance.max(speed));
+        if distance <= tolerance {
+            break;
+        }
+
+        // Once advanced, the normal between nearly touching shapes is mostly rounding noise.
+        // Not closing along it only proves the shapes never meet at the start or once the
+        // distance stops shrinking. Otherwise take the step that is safe in any direction.
+        let approach = -relative.dot(&normal);
+        let step = if approach > T::zero() {
+            distance / approach
+        } else if time == T::zero() || distance >= last_distance {
+            return None;
+        } else {
+            distance / speed
+        };
+        last_distance = distance;
+        time = time + step;
+        if time > T::one() {
+            return None;
+        }
+        point = Some(closest.second + velocity_b * step);
+    }
+
+    let point = point.unwrap_or_else(|| b.support(normal) + velocity_b * time);
+    Some(TimeOfImpact {
+        time,
+        normal,
+        point,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::time_of_impact;
+    use crate::geometry::{Aabb3f64, ConvexHull, Plane, Sphere, Triangle};
+    use crate::test_util::{assert_vec_near, v3};
+    use crate::vector::vector_ops::Vector3Ops;
+
+    fn unit_box() -> Aabb3f64 {
+        Aabb3f64::new(v3(-1.0, -1.0, -1.0), v3(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn test_sphere_sweep_plane() {
+        let ground = Plane::new(v3(0.0, 2.0, 0.0), 0.0);
+        let sphere = Sphere::new(v3(0.0, 5.0, 0.0), 1.0);
+        let hit = sphere.sweep_plane(v3(0.0, -8.0, 0.0), &ground).unwrap();
+        assert_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, v3(0.0, 1.0, 0.0));
+        assert_eq!(hit.point, v3(0.0, 0.0, 0.0));
+
+        let below = Sphere::new(v3(0.0, -5.0, 0.0), 1.0);
+        let hit = below.sweep_plane(v3(0.0, 8.0, 0.0), &ground).unwrap();
+        assert_eq!(hit.normal, v3(0.0, -1.0, 0.0));
+
+        assert!(sphere.sweep_plane(v3(0.0, 8.0, 0.0), &ground).is_none());
+        assert!(sphere.sweep_plane(v3(0.0, -2.0, 0.0), &ground).is_none());
+        let resting = Sphere::new(v3(0.0, 0.5, 0.0), 1.0);
+        assert_eq!(
+            resting
+                .sweep_plane(v3(1.0, 0.0, 0.0), &ground)
+                .unwrap()
+                .time,
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_sphere_sweep_triangle() {
+        let triangle = Triangle::new(v3(0.0, 0.0, 0.0), v3(4.0, 0.0, 0.0), v3(0.0, 4.0, 0.0));
+
+        // Face, edge and vertex contacts
+        let face = Sphere::new(v3(1.0, 1.0, 5.0), 1.0);
+        let hit = face.sweep_triangle(v3(0.0, 0.0, -8.0), &triangle).unwrap();
+        assert!((hit.time - 0.5).abs() < 1e-12);
+        assert_vec_near(hit.normal, v3(0.0, 0.0, 1.0), 1e-12);
+
+        let edge = Sphere::new(v3(2.0, -3.0, 0.0), 1.0);
+        let hit = edge.sweep_triangle(v3(0.0, 4.0, 0.0), &triangle).unwrap();
+        assert!((hit.time - 0.5).abs() < 1e-12);
+        assert_vec_near(hit.normal, v3(0.0, -1.0, 0.0), 1e-12);
+        assert_vec_near(hit.point, v3(2.0, 0.0, 0.0), 1e-12);
+
+        let vertex = Sphere::new(v3(-3.0, -3.0, 0.0), 1.0);
+        let hit = vertex.sweep_triangle(v3(4.0, 4.0, 0.0), &triangle).unwrap();
+        assert!((hit.time - (3.0 - 0.5f64.sqrt()) / 4.0).abs() < 1e-12);
+        assert_vec_near(hit.point, v3(0.0, 0.0, 0.0), 1e-12);
+
+        // A fast sphere passing straight through still hits the thin triangle
+        let fast = Sphere::new(v3(1.0, 1.0, 50.0), 0.1);
+        assert!(
+            fast.sweep_triangle(v3(0.0, 0.0, -100.0), &triangle)
+                .is_some()
+        );
+        assert!(face.sweep_triangle(v3(10.0, 0.0, 0.0), &triangle).is_none());
+    }
+
+    #[test]
+    fn test_sphere_sweep_aabb() {
+        let sphere = Sphere::new(v3(5.0, 0.0, 0.0), 0.5);
+        let hit = sphere.sweep_aabb(v3(-8.0, 0.0, 0.0), &unit_box()).unwrap();
+        assert!((hit.time - 3.5 / 8.0).abs() < 1e-12);
+        assert_vec_near(hit.normal, v3(1.0, 0.0, 0.0), 1e-12);
+        assert_vec_near(hit.point, v3(1.0, 0.0, 0.0), 1e-12);
+
+        let corner = Sphere::new(v3(5.0, 5.0, 5.0), 0.5);
+        let hit = corner
+            .sweep_aabb(v3(-8.0, -8.0, -8.0), &unit_box())
+            .unwrap();
+        assert!((hit.time - (4.0 - 0.5 / 3.0f64.sqrt()) / 8.0).abs() < 1e-12);
+        assert_vec_near(hit.normal, v3(1.0, 1.0, 1.0).normalize(), 1e-12);
+        assert_vec_near(hit.point, v3(1.0, 1.0, 1.0), 1e-12);
+
+        let edge = Sphere::new(v3(5.0, 5.0, 0.0), 0.5);
+        let hit = edge.sweep_aabb(v3(-8.0, -8.0, 0.0), &unit_box()).unwrap();
+        assert!((hit.time - (4.0 - 0.5 / 2.0f64.sqrt()) / 8.0).abs() < 1e-12);
+        assert_vec_near(hit.point, v3(1.0, 1.0, 0.0), 1e-12);
+
+        // Passes beside the rounded corner
+        let beside = Sphere::new(v3(5.0, 1.4, 1.4), 0.5);
+        assert!(beside.sweep_aabb(v3(-8.0, 0.0, 0.0), &unit_box()).is_none());
+        let past = Sphere::new(v3(5.0, 3.0, 0.0), 0.5);
+        assert!(past.sweep_aabb(v3(-8.0, 0.0, 0.0), &unit_box()).is_none());
+    }
+
+    #[test]
+    fn test_aabb_sweep_aabb() {
+        let wall = Aabb3f64::new(v3(4.0, -1.0, -1.0), v3(6.0, 1.0, 1.0));
+        let hit = unit_box().sweep_aabb(v3(10.0, 0.5, 0.0), &wall).unwrap();
+        assert!((hit.time - 0.3).abs() < 1e-12);
+        assert_eq!(hit.normal, v3(-1.0, 0.0, 0.0));
+        assert!((hit.point[0] - 4.0).abs() < 1e-12);
+
+        let above = Aabb3f64::new(v3(4.0, 3.0, -1.0), v3(6.0, 5.0, 1.0));
+        assert!(unit_box().sweep_aabb(v3(10.0, 0.0, 0.0), &above).is_none());
+        assert!(unit_box().sweep_aabb(v3(2.0, 0.0, 0.0), &wall).is_none());
+        assert!(unit_box().sweep_aabb(v3(-10.0, 0.0, 0.0), &wall).is_none());
+
+        let overlapping = Aabb3f64::new(v3(0.8, -1.0, -1.0), v3(3.0, 1.0, 1.0));
+        let hit = unit_box()
+            .sweep_aabb(v3(1.0, 0.0, 0.0), &overlapping)
+            .unwrap();
+        assert_eq!(hit.time, 0.0);
+        assert_eq!(hit.normal, v3(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_time_of_impact() {
+        let a = Sphere::new(v3(0.0, 0.0, 0.0), 1.0);
+        let b = Sphere::new(v3(5.0, 0.0, 0.0), 1.0);
+        let still = v3(0.0, 0.0, 0.0);
+        let hit = time_of_impact(&a, v3(10.0, 0.0, 0.0), &b, still).unwrap();
+        assert!((hit.time - 0.3).abs() < 1e-6);
+        assert_vec_near(hit.normal, v3(-1.0, 0.0, 0.0), 1e-6);
+        assert_vec_near(hit.point, v3(4.0, 0.0, 0.0), 1e-6);
+
+        let hit = time_of_impact(&a, v3(10.0, 0.0, 0.0), &b, v3(-10.0, 0.0, 0.0)).unwrap();
+        assert!((hit.time - 0.15).abs() < 1e-6);
+        assert!(time_of_impact(&a, v3(0.0, 10.0, 0.0), &b, still).is_none());
+        assert!(time_of_impact(&a, v3(2.0, 0.0, 0.0), &b, still).is_none());
+
+        // Tunneling: a small fast sphere against a thin wall
+        let bullet = Sphere::new(v3(0.0, 0.0, 0.0), 0.1);
+        let wall = Aabb3f64::new(v3(4.0, -1.0, -1.0), v3(4.01, 1.0, 1.0));
+        let hit = time_of_impact(&bullet, v3(20.0, 0.0, 0.0), &wall, still).unwrap();
+        assert!((hit.time - 3.9 / 20.0).abs() < 1e-6);
+
+        // Agrees with the exact sweep for a glancing corner contact
+        let corner = Sphere::new(v3(5.0, 4.0, 3.0), 0.5);
+        let motion = v3(-8.0, -7.0, -6.0);
+        let exact = corner.sweep_aabb(motion, &unit_box()).unwrap();
+        let advanced = time_of_impact(&corner, motion, &unit_box(), still).unwrap();
+        assert!((exact.time - advanced.time).abs() < 1e-6);
+        assert_vec_near(exact.normal, advanced.normal, 1e-4);
+
+        let points = [v3(3.0, -1.0, -1.0), v3(3.0, 1.0, -1.0), v3(3.0, 0.0, 1.0)];
+        let hull = ConvexHull::new(&points);
+        let hit = time_of_impact(&hull, v3(-4.0, 0.0, 0.0), &unit_box(), still).unwrap();
+        assert!((hit.time - 0.5).abs() < 1e-6);
+        assert_vec_near(hit.normal, v3(1.0, 0.0, 0.0), 1e-6);
+
+        let overlapping = time_of_impact(&a, v3(1.0, 0.0, 0.0), &unit_box(), still).unwrap();
+        assert_eq!(overlapping.time, 0.0);
+    }
+
+    #[test]
+    fn test_time_of_impact_matches_sphere_sweep() {
+        let still = v3(0.0, 0.0, 0.0);
+        // The distance stalls just above the tolerance near contact here
+        let sphere = Sphere::new(v3(5.6767, -4.3862, -0.1939), 0.2259);
+        let motion = v3(-9.543, 5.836, -1.573);
+        let aabb = Aabb3f64::new(v3(-1.518, -1.250, -0.806), v3(0.883, 0.199, 0.616));
+        let exact = sphere.sweep_aabb(motion, &aabb).unwrap();
+        let advanced = time_of_impact(&sphere, motion, &aabb, still).unwrap();
+        assert!((exact.time - advanced.time).abs() < 1e-6);
+
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = |low: f64, high: f64| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            low + (state >> 11) as f64 / (1u64 << 53) as f64 * (high - low)
+        };
+        for _ in 0..1000 {
+            let center = v3(next(-6.0, 6.0), next(-6.0, 6.0), next(-6.0, 6.0));
+            let sphere = Sphere::new(center, next(0.1, 1.0));
+            // Aimed near the box so that about a quarter of the sweeps hit
+            let target = v3(next(-3.0, 3.0), next(-3.0, 3.0), next(-3.0, 3.0));
+            let motion = (target - center) * next(0.5, 2.0);
+            let min = v3(next(-2.0, 1.0), next(-2.0, 1.0), next(-2.0, 1.0));
+            let aabb = Aabb3f64::new(
+                min,
+                min + v3(next(0.2, 3.0), next(0.2, 3.0), next(0.2, 3.0)),
+            );
+            let exact = sphere.sweep_aabb(motion, &aabb);
+            let advanced = time_of_impact(&sphere, motion, &aabb, still);
+            match (exact, advanced) {
+                (Some(exact), Some(advanced)) => assert!(
+                    (exact.time - advanced.time).abs() < 1e-5,
+                    "{:?} {:?} {:?}",
+                    sphere,
+                    motion,
+                    aabb
+                ),
+                (None, None) => {}
+                _ => panic!("{:?} {:?} {:?}", sphere, motion, aabb),
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use num_traits::Float;
+
+use crate::geometry::Aabb3;
+use crate::vector::vector_ops::{Vector2Ops, Vector3Ops};
+use crate::vector::{Vector2, Vector3};
+
+/// Triangle with counter-clockwise winding `a`, `b`, `c` seen from the front
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle<T> {
+    pub a: Vector3<T>,
+    pub b: Vector3<T>,
+    pub c: Vector3<T>,
+}
+
+impl<T: Float> Triangle<T> {
+    pub fn new(a: Vector3<T>, b: Vector3<T>, c: Vector3<T>) -> Self {
+        Triangle { a, b, c }
+    }
+
+    /// Unit normal of the front face
+    pub fn normal(&self) -> Vector3<T> {
+        (self.b - self.a).cross(&(self.c - self.a)).normalize()
+    }
+
+    pub fn area(&self) -> T {
+        let half = T::one() / (T::one() + T::one());
+        (self.b - self.a).cross(&(self.c - self.a)).length() * half
+    }
+
+    /// Point with the given barycentric weights of `a`, `b` and `c`
+    pub fn from_barycentric(&self, weights: Vector3<T>) -> Vector3<T> {
+        self.a * weights[0] + self.b * weights[1] + self.c * weights[2]
+    }
+
+    pub fn bounds(&self) -> Aabb3<T> {
+        Aabb3::new(self.a, self.a).expand(self.b).expand(self.c)
+    }
+}
+
+/// 2D triangle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle2<T> {
+    pub a: Vector2<T>,
+    pub b: Vector2<T>,
+    pub c: Vector2<T>,
+}
+
+impl<T: Float> Triangle2<T> {
+    pub fn new(a: Vector2<T>, b: Vector2<T>, c: Vector2<T>) -> Self {
+        Triangle2 { a, b, c }
+    }
+
+    /// Area, positive for counter-clockwise winding
+    pub fn signed_area(&self) -> T {
+        let half = T::one() / (T::one() + T::one());
+        let (ab, ac) = (self.b - self.a, self.c - self.a);
+        (ab.x() * ac.y() - ab.y() * ac.x()) * half
+    }
+}
@@ -0,0 +1,189 @@
+use num_traits::Float;
+
+use crate::matrix::Matrix4x4;
+use crate::matrix::transfom_traits::{Convention, DepthRange, VectorConvention};
+use crate::vector::vector_ops::Vector3Ops;
+use crate::vector::{Vector2, Vector3, Vector4};
+
+/// Screen rectangle that normalized device coordinates map onto.
+///
+/// Screen coordinates have their origin at the top-left corner with y pointing down, and
+/// depth in [0, 1]. `convention` must match the one the projection was built with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport<T> {
+    pub x: T,
+    pub y: T,
+    pub width: T,
+    pub height: T,
+    pub convention: Convention,
+}
+
+impl<T: Float> Viewport<T> {
+    /// Viewport for projections built with the default convention
+    pub fn new(x: T, y: T, width: T, height: T) -> Self {
+        Viewport {
+            x,
+            y,
+            width,
+            height,
+            convention: Convention::default(),
+        }
+    }
+
+    pub fn with_convention(self, convention: Convention) -> Self {
+        Viewport { convention, ..self }
+    }
+
+    /// Screen position and depth of a world-space point. Returns `None` for points behind
+    /// the camera.
+    pub fn project(&self, world_point: Vector3<T>, view_proj: &Matrix4x4<T>) -> Option<Vector3<T>> {
+        let clip = self.column_form(view_proj)
+            * Vector4::new(world_point.x(), world_point.y(), world_point.z(), T::one());
+        if clip[3] <= T::zero() {
+            return None;
+        }
+
+        let two = T::one() + T::one();
+        let ndc = |i: usize| clip[i] / clip[3];
+        let depth = match self.convention.depth {
+            DepthRange::ZeroToOne => ndc(2),
+            DepthRange::NegativeOneToOne => (ndc(2) + T::one()) / two,
+        };
+        Some(Vector3::new(
+            self.x + (ndc(0) + T::one()) / two * self.width,
+            self.y + (T::one() - ndc(1)) / two * self.height,
+            depth,
+        ))
+    }
+
+    /// World-space point at a screen position and depth, the inverse of `project`
+    pub fn unproject(
+        &self,
+        screen_point: Vector2<T>,
+        depth: T,
+        inverse_view_proj: &Matrix4x4<T>,
+    ) -> Vector3<T> {
+        let two = T::one() + T::one();
+        let ndc_z = match self.convention.depth {
+            DepthRange::ZeroToOne => depth,
+            DepthRange::NegativeOneToOne => depth * two - T::one(),
+        };
+        let ndc = Vector3::new(
+            (screen_point[0] - self.x) / self.width * two - T::one(),
+            T::one() - (screen_point[1] - self.y) / self.height * two,
+            ndc_z,
+        );
+        self.column_form(inverse_view_proj).transform_point(ndc)
+    }
+
+    /// Picking ray through a screen position, as an origin on the near plane and a unit
+    /// direction away from the camera. Works with infinite far planes.
+    pub fn screen_ray(
+        &self,
+        screen_point: Vector2<T>,
+        inverse_view_proj: &Matrix4x4<T>,
+    ) -> (Vector3<T>, Vector3<T>) {
+        let half = T::one() / (T::one() + T::one());
+        // Depth halfway through the range is finite even when the far plane is not
+        let near_depth = if self.convention.reverse_z {
+            T::one()
+        } else {
+            T::zero()
+        };
+        let origin = self.unproject(screen_point, near_depth, inverse_view_proj);
+        let toward = self.unproject(screen_point, half, inverse_view_proj);
+        (origin, (toward - origin).normalize())
+    }
+
+    fn column_form(&self, m: &Matrix4x4<T>) -> Matrix4x4<T> {
+        match self.convention.vectors {
+            VectorConvention::Column => *m,
+            VectorConvention::Row => m.transpose(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Viewport;
+    use crate::Matrix4x4f64;
+    use crate::matrix::transfom_traits::{Convention, Transform4x4, VectorConvention};
+    use crate::vector::vector_ops::Vector3Ops;
+    use crate::vector::{Vector2f64, Vector3f64};
+
+    fn camera(convention: Convention) -> Matrix4x4f64 {
+        let eye = Vector3f64::new(1.0, 2.0, 10.0);
+        let target = Vector3f64::new(0.0, 0.0, 0.0);
+        let up = Vector3f64::new(0.0, 1.0, 0.0);
+        let projection = Matrix4x4f64::perspective_with(0.8, 1.5, 0.5, 100.0, convention);
+        let view = Matrix4x4f64::look_at_with(eye, target, up, convention);
+        match convention.vectors {
+            VectorConvention::Column => projection * view,
+            VectorConvention::Row => view * projection,
+        }
+    }
+
+    #[test]
+    fn test_project_unproject_round_trip() {
+        let conventions = [
+            Convention::default(),
+            Convention::OPENGL,
+            Convention::LEFT_HANDED_ROW,
+            Convention::default().with_reverse_z(true),
+        ];
+        for convention in conventions {
+            let view_proj = camera(convention);
+            let inverse = view_proj.inverse().unwrap();
+            let viewport = Viewport::new(10.0, 20.0, 1200.0, 800.0).with_convention(convention);
+
+            let world = Vector3f64::new(0.5, -0.25, 1.0);
+            let screen = viewport.project(world, &view_proj).unwrap();
+            assert!(screen[2] > 0.0 && screen[2] < 1.0);
+            let back =
+                viewport.unproject(Vector2f64::new(screen[0], screen[1]), screen[2], &inverse);
+            assert!((back - world).length() < 1e-9);
+
+            // The camera target is at the viewport center
+            let center = viewport
+                .project(Vector3f64::new(0.0, 0.0, 0.0), &view_proj)
+                .unwrap();
+            assert!((center[0] - 610.0).abs() < 1e-9 && (center[1] - 420.0).abs() < 1e-9);
+
+            // Points behind the camera do not project
+            assert!(
+                viewport
+                    .project(Vector3f64::new(2.0, 4.0, 20.0), &view_proj)
+                    .is_none()
+            );
+        }
+    }
+
+    #[test]
+    fn test_screen_ray() {
+        let convention = Convention::default();
+        let view_proj = camera(convention);
+        let inverse = view_proj.inverse().unwrap();
+        let viewport = Viewport::new(0.0, 0.0, 640.0, 480.0);
+
+        // A ray through the projection of a point passes through that point
+        let world = Vector3f64::new(-1.0, 0.5, 2.0);
+        let screen = viewport.project(world, &view_proj).unwrap();
+        let (origin, direction) =
+            viewport.screen_ray(Vector2f64::new(screen[0], screen[1]), &inverse);
+        assert!((direction.length() - 1.0).abs() < 1e-12);
+        let to_point = world - origin;
+        assert!(to_point.cross(&direction).length() < 1e-9);
+        assert!(to_point.dot(&direction) > 0.0);
+
+        // Infinite far plane with reverse-Z
+        let reversed = Convention::default().with_reverse_z(true);
+        let projection = Matrix4x4f64::perspective_infinite_with(1.0, 1.0, 0.1, reversed);
+        let viewport = viewport.with_convention(reversed);
+        let (origin, direction) = viewport.screen_ray(
+            Vector2f64::new(320.0, 240.0),
+            &projection.inverse().unwrap(),
+        );
+        assert!((origin - Vector3f64::new(0.0, 0.0, -0.1)).length() < 1e-12);
+        assert!((direction - Vector3f64::new(0.0, 0.0, -1.0)).length() < 1e-12);
+    }
+}
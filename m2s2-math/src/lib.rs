@@ -1,24 +1,64 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("m2s2-math needs either the `std` or the `libm` feature for floating point math");
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod euler;
+mod geometry;
 mod matrix;
 mod quaternian;
+#[cfg(feature = "alloc")]
+mod spatial;
+#[cfg(test)]
+mod test_util;
 mod vector;
 
+pub use crate::euler::{EulerFrame, EulerOrder};
+pub use crate::geometry::{
+    Aabb, Aabb2, Aabb2f32, Aabb2f64, Aabb3, Aabb3f32, Aabb3f64, Capsule, Capsule2, Capsule3,
+    Circle, ClosestPoints, Containment, ConvexHull, Frustum, Obb, Obb2, Penetration, Plane,
+    PointQuery, Ray, Ray2, Ray2f32, Ray2f64, Ray3, Ray3f32, Ray3f64, RayHit, Segment, Segment2,
+    Segment3, Sphere, SupportMap, TimeOfImpact, Triangle, Triangle2, TriangleHit, Viewport,
+    epa_penetration, gjk_distance, gjk_intersects, time_of_impact,
+};
+pub use crate::matrix::{AffineParts, Cholesky, Lu, Qr, Svd, SymmetricEigen};
 pub use crate::matrix::{
-    Matrix2x2f32, Matrix2x2f64, Matrix2x2i32, Matrix2x2i64, Matrix3x3f32, Matrix3x3f64,
-    Matrix3x3i32, Matrix3x3i64, Matrix4x4f32, Matrix4x4f64, Matrix4x4i32, Matrix4x4i64,
+    Matrix, Matrix2x2, Matrix2x2f32, Matrix2x2f64, Matrix2x2i32, Matrix2x2i64, Matrix2x3,
+    Matrix2x3f32, Matrix2x3f64, Matrix2x3i32, Matrix2x3i64, Matrix2x4, Matrix2x4f32, Matrix2x4f64,
+    Matrix2x4i32, Matrix2x4i64, Matrix3x2, Matrix3x2f32, Matrix3x2f64, Matrix3x2i32, Matrix3x2i64,
+    Matrix3x3, Matrix3x3f32, Matrix3x3f64, Matrix3x3i32, Matrix3x3i64, Matrix3x4, Matrix3x4f32,
+    Matrix3x4f64, Matrix3x4i32, Matrix3x4i64, Matrix4x2, Matrix4x2f32, Matrix4x2f64, Matrix4x2i32,
+    Matrix4x2i64, Matrix4x3, Matrix4x3f32, Matrix4x3f64, Matrix4x3i32, Matrix4x3i64, Matrix4x4,
+    Matrix4x4f32, Matrix4x4f64, Matrix4x4i32, Matrix4x4i64,
 };
+pub use crate::quaternian::{Quaternion, Quaternionf32, Quaternionf64};
+#[cfg(feature = "alloc")]
+pub use crate::spatial::{Bvh, KdTree, KdTree2, KdTree3, LooseTree, Neighbor, Octree, Quadtree};
 pub use crate::vector::{
     Vector, Vector2, Vector2f32, Vector2f64, Vector2i32, Vector2i64, Vector3, Vector3f32,
     Vector3f64, Vector3i32, Vector3i64, Vector4, Vector4f32, Vector4f64, Vector4i32, Vector4i64,
 };
 
 // Export graphics traits for users who want to use them directly
-pub use crate::matrix::transfom_traits::{Transform2x2, Transform3x3, Transform4x4};
+pub use crate::matrix::transfom_traits::{
+    Convention, DepthRange, Handedness, Transform2x2, Transform3x3, Transform4x4, VectorConvention,
+};
 pub use crate::vector::vector_ops::{Vector2Ops, Vector3Ops};
 
 #[cfg(test)]
 mod tests {
+    use crate::test_util::assert_vec_near;
+    use crate::{Convention, DepthRange, Handedness, Vector2, Vector3, VectorConvention};
     use crate::{Matrix2x2f32, Matrix2x2i32, Matrix4x4f32, Matrix4x4i32};
-    use crate::{Vector2i32, Vector3f32, Vector3i32, Vector4f32, Vector4i32};
+    use crate::{Matrix2x3i32, Matrix3x2i32, Matrix3x4f32, Matrix4x3f32};
+    use crate::{Matrix3x3f64, Matrix3x3i32, Matrix4x4f64, Transform3x3, Transform4x4};
+    use crate::{Vector2f64, Vector3Ops};
+    use crate::{
+        Vector2i32, Vector3f32, Vector3f64, Vector3i32, Vector4f32, Vector4f64, Vector4i32,
+    };
     // --- Vector Tests ---
     #[test]
     fn test_vector_new_i32() {
@@ -351,4 +391,333 @@ mod tests {
                                   // Expected: [[1*5+2*7, 1*6+2*8], [3*5+4*7, 3*6+4*8]] = [[5+14, 6+16], [15+28, 18+32]] = [[19, 22], [43, 50]]
         assert_eq!(result.as_slice(), [19, 22, 43, 50]);
     }
+
+    #[test]
+    fn test_matrix_transpose() {
+        let mat = Matrix3x3i32::from_2d_array([[1, 2, 3], [4, 5, 6], [7, 8, 9]]);
+        assert_eq!(mat.transpose().as_slice(), [1, 4, 7, 2, 5, 8, 3, 6, 9]);
+        assert_eq!(mat.transpose().transpose(), mat);
+    }
+
+    #[test]
+    fn test_matrix_determinant() {
+        assert_eq!(
+            Matrix2x2i32::from_2d_array([[1, 2], [3, 4]]).determinant(),
+            -2
+        );
+        assert_eq!(
+            Matrix3x3i32::from_2d_array([[2, 0, 1], [1, 3, 2], [1, 1, 2]]).determinant(),
+            6
+        );
+        let mat =
+            Matrix4x4i32::from_2d_array([[1, 0, 2, -1], [3, 0, 0, 5], [2, 1, 4, -3], [1, 0, 5, 0]]);
+        assert_eq!(mat.determinant(), 30);
+        assert_eq!(mat.transpose().determinant(), 30);
+    }
+
+    #[test]
+    fn test_matrix_adjugate() {
+        let mat = Matrix3x3i32::from_2d_array([[2, 0, 1], [1, 3, 2], [1, 1, 2]]);
+        // A * adj(A) = det(A) * I
+        assert_eq!(
+            mat * mat.adjugate(),
+            Matrix3x3i32::identity() * mat.determinant()
+        );
+    }
+
+    #[test]
+    fn test_matrix_inverse() {
+        let mat = Matrix4x4f64::from_2d_array([
+            [1.0, 0.0, 2.0, -1.0],
+            [3.0, 0.0, 0.0, 5.0],
+            [2.0, 1.0, 4.0, -3.0],
+            [1.0, 0.0, 5.0, 0.0],
+        ]);
+        let product = mat * mat.inverse().unwrap();
+        for (a, b) in product
+            .as_slice()
+            .iter()
+            .zip(Matrix4x4f64::identity().as_slice())
+        {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        let inv2 = Matrix2x2f32::from_2d_array([[4.0, 7.0], [2.0, 6.0]])
+            .inverse()
+            .unwrap();
+        assert_eq!(inv2.as_slice(), [0.6, -0.7, -0.2, 0.4]);
+
+        let singular =
+            Matrix3x3f64::from_2d_array([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 1.0]]);
+        assert_eq!(singular.inverse(), None);
+    }
+
+    #[test]
+    fn test_matrix4x4_inverse_affine() {
+        let transform = Matrix4x4f64::translation(Vector3f64::new(1.0, -2.0, 3.0))
+            * Matrix4x4f64::rotation_axis_angle(Vector3f64::new(1.0, 1.0, 0.0), 0.7)
+            * Matrix4x4f64::scale(Vector3f64::new(2.0, 3.0, 0.5));
+        let fast = transform.inverse_affine().unwrap();
+        let general = transform.inverse().unwrap();
+        for (a, b) in fast.as_slice().iter().zip(general.as_slice()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+
+        assert_eq!(
+            Matrix4x4f64::scale(Vector3f64::new(1.0, 0.0, 1.0)).inverse_affine(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_rectangular_matrix_mul() {
+        let a = Matrix2x3i32::from_2d_array([[1, 2, 3], [4, 5, 6]]);
+        let b = Matrix3x2i32::from_2d_array([[7, 8], [9, 10], [11, 12]]);
+        let ab = a * b; // 2x3 * 3x2 = 2x2
+        assert_eq!(ab.as_slice(), [58, 64, 139, 154]);
+        assert_eq!((ab.rows(), ab.cols()), (2, 2));
+
+        let ba = b * a; // 3x2 * 2x3 = 3x3
+        assert_eq!(ba.as_slice(), [39, 54, 69, 49, 68, 87, 59, 82, 105]);
+
+        let wide = Matrix3x4f32::from_slice(&[
+            1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0,
+        ]);
+        let tall: Matrix4x3f32 = wide.transpose();
+        assert_eq!((tall.rows(), tall.cols()), (4, 3));
+        assert_eq!(tall[3], [4.0, 8.0, 12.0]);
+    }
+
+    #[test]
+    fn test_rectangular_matrix_mul_vector() {
+        let a = Matrix2x3i32::from_2d_array([[1, 2, 3], [4, 5, 6]]);
+        let v: Vector2<i32> = a * Vector3::new(1, 0, -1);
+        assert_eq!(v.as_slice(), [-2, -2]);
+
+        let drop_w = Matrix3x4f32::from_2d_array([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ]);
+        let widen: Matrix4x3f32 = drop_w.transpose();
+        let round_trip = drop_w * (widen * Vector3f32::new(1.0, 2.0, 3.0));
+        assert_eq!(round_trip.as_slice(), [1.0, 2.0, 3.0]);
+    }
+
+    // --- Transform Tests ---
+    #[test]
+    fn test_look_at_composes_with_translation() {
+        let eye = Vector3f64::new(1.0, 2.0, 3.0);
+        let target = Vector3f64::new(1.0, 2.0, -2.0);
+        let up = Vector3f64::new(0.0, 1.0, 0.0);
+        let view = Matrix4x4f64::look_at(eye, target, up);
+
+        // Eye maps to the origin and the target lies down -Z
+        assert_vec_near(
+            view * Vector4f64::new(1.0, 2.0, 3.0, 1.0),
+            Vector4f64::new(0.0, 0.0, 0.0, 1.0),
+            1e-12,
+        );
+        assert_vec_near(
+            view * Vector4f64::new(1.0, 2.0, -2.0, 1.0),
+            Vector4f64::new(0.0, 0.0, -5.0, 1.0),
+            1e-12,
+        );
+
+        // Undoing the camera placement gives back the identity
+        let camera = Matrix4x4f64::translation(eye);
+        let product = view * camera;
+        for (a, b) in product
+            .as_slice()
+            .iter()
+            .zip(Matrix4x4f64::identity().as_slice())
+        {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_look_at_handedness() {
+        let eye = Vector3f64::new(0.0, 0.0, 0.0);
+        let target = Vector3f64::new(0.0, 0.0, 4.0);
+        let up = Vector3f64::new(0.0, 1.0, 0.0);
+        let lh = Convention::new(VectorConvention::Column, Handedness::Left);
+        let view = Matrix4x4f64::look_at_with(eye, target, up, lh);
+
+        // Left-handed cameras look down +Z
+        assert_vec_near(
+            view * Vector4f64::new(0.0, 0.0, 4.0, 1.0),
+            Vector4f64::new(0.0, 0.0, 4.0, 1.0),
+            1e-12,
+        );
+        assert_vec_near(
+            view * Vector4f64::new(1.0, 0.0, 4.0, 1.0),
+            Vector4f64::new(1.0, 0.0, 4.0, 1.0),
+            1e-12,
+        );
+        assert!((view.determinant() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_projection_handedness() {
+        let lh = Convention::new(VectorConvention::Column, Handedness::Left);
+        let rh = Matrix4x4f64::perspective(1.0, 1.5, 0.1, 100.0);
+        let lh = Matrix4x4f64::perspective_with(1.0, 1.5, 0.1, 100.0, lh);
+
+        // The same point in front of the camera, in each handedness
+        let in_rh = rh * Vector4f64::new(0.5, 0.5, -10.0, 1.0);
+        let in_lh = lh * Vector4f64::new(0.5, 0.5, 10.0, 1.0);
+        assert_vec_near(in_rh, in_lh, 1e-12);
+        assert!(in_rh[3] > 0.0);
+    }
+
+    #[test]
+    fn test_row_vector_convention() {
+        let eye = Vector3f64::new(1.0, 2.0, 3.0);
+        let target = Vector3f64::new(0.0, 0.0, 0.0);
+        let up = Vector3f64::new(0.0, 1.0, 0.0);
+        let column = Matrix4x4f64::look_at(eye, target, up);
+        let row = Matrix4x4f64::look_at_with(eye, target, up, Convention::LEFT_HANDED_ROW);
+        let row_rh = Matrix4x4f64::look_at_with(
+            eye,
+            target,
+            up,
+            Convention::new(VectorConvention::Row, Handedness::Right),
+        );
+        assert_eq!(row_rh, column.transpose());
+        assert_eq!(row.transpose()[3][3], 1.0);
+        assert_eq!(column.into_convention(VectorConvention::Row), row_rh);
+    }
+
+    #[test]
+    fn test_as_column_major_array() {
+        let m = Matrix4x4f32::translation(Vector3f32::new(5.0, 6.0, 7.0));
+        let columns = m.as_column_major_array();
+        assert_eq!(columns[3], [5.0, 6.0, 7.0, 1.0]);
+        assert_eq!(
+            columns.as_flattened(),
+            [
+                1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 5.0, 6.0, 7.0, 1.0
+            ]
+        );
+    }
+
+    // NDC depth of a view-space point straight ahead of a right-handed camera
+    fn ndc_depth(projection: Matrix4x4f64, distance: f64) -> f64 {
+        let clip = projection * Vector4f64::new(0.0, 0.0, -distance, 1.0);
+        clip[2] / clip[3]
+    }
+
+    #[test]
+    fn test_projection_depth_ranges() {
+        let (near, far) = (0.5, 50.0);
+        let gl = Convention::OPENGL;
+        let reversed = Convention::default().with_reverse_z(true);
+        let reversed_gl = gl.with_reverse_z(true);
+
+        let cases = [
+            (Convention::default(), 0.0, 1.0),
+            (gl, -1.0, 1.0),
+            (reversed, 1.0, 0.0),
+            (reversed_gl, 1.0, -1.0),
+        ];
+        for (convention, at_near, at_far) in cases {
+            let p = Matrix4x4f64::perspective_with(1.2, 1.0, near, far, convention);
+            assert!((ndc_depth(p, near) - at_near).abs() < 1e-12);
+            assert!((ndc_depth(p, far) - at_far).abs() < 1e-12);
+
+            let o = Matrix4x4f64::orthographic_with(-1.0, 1.0, -1.0, 1.0, near, far, convention);
+            assert!((ndc_depth(o, near) - at_near).abs() < 1e-12);
+            assert!((ndc_depth(o, far) - at_far).abs() < 1e-12);
+        }
+        assert_eq!(gl.depth, DepthRange::NegativeOneToOne);
+
+        // The convention-free orthographic builder keeps OpenGL depth
+        let o = Matrix4x4f64::orthographic(-1.0, 1.0, -1.0, 1.0, near, far);
+        assert_eq!(o[2][2], -2.0 / (far - near));
+        assert_eq!(o[2][3], -(far + near) / (far - near));
+        assert!((ndc_depth(o, near) + 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_perspective_infinite() {
+        let near = 0.1;
+        let p = Matrix4x4f64::perspective_infinite(1.0, 1.0, near);
+        assert!(ndc_depth(p, near).abs() < 1e-12);
+        assert!((ndc_depth(p, 1e9) - 1.0).abs() < 1e-9);
+
+        // Reverse-Z infinite projection maps infinity exactly to zero
+        let reversed = Convention::default().with_reverse_z(true);
+        let p = Matrix4x4f64::perspective_infinite_with(1.0, 1.0, near, reversed);
+        assert_eq!(p[2][2], 0.0);
+        assert!((ndc_depth(p, near) - 1.0).abs() < 1e-12);
+
+        // Matches the finite projection's x and y scaling
+        let finite = Matrix4x4f64::perspective(1.0, 1.0, near, 100.0);
+        assert!((p[0][0] - finite[0][0]).abs() < 1e-12);
+        assert!((p[1][1] - finite[1][1]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_frustum_off_center() {
+        let (left, right, bottom, top, near, far) = (-0.2, 0.6, -0.1, 0.3, 0.5, 20.0);
+        let lh = Convention::new(VectorConvention::Column, Handedness::Left);
+        for (convention, z) in [(Convention::default(), -near), (lh, near)] {
+            let f = Matrix4x4f64::frustum_with(left, right, bottom, top, near, far, convention);
+            let lower = f * Vector4f64::new(left, bottom, z, 1.0);
+            let upper = f * Vector4f64::new(right, top, z, 1.0);
+            assert!((lower[0] / lower[3] + 1.0).abs() < 1e-12);
+            assert!((lower[1] / lower[3] + 1.0).abs() < 1e-12);
+            assert!((upper[0] / upper[3] - 1.0).abs() < 1e-12);
+            assert!((upper[1] / upper[3] - 1.0).abs() < 1e-12);
+        }
+
+        // A symmetric frustum is the same as the field-of-view form
+        let fov: f64 = 1.0;
+        let half = near * (fov / 2.0).tan();
+        let f = Matrix4x4f64::frustum(-half, half, -half, half, near, far);
+        let p = Matrix4x4f64::perspective(fov, 1.0, near, far);
+        for (a, b) in f.as_slice().iter().zip(p.as_slice()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+
+    // --- Point / Direction Transform Tests ---
+    #[test]
+    fn test_transform_point_and_vector() {
+        let m = Matrix4x4f64::translation(Vector3f64::new(1.0, 2.0, 3.0))
+            * Matrix4x4f64::scale(Vector3f64::new(2.0, 2.0, 2.0));
+        let v = Vector3f64::new(1.0, 0.0, -1.0);
+        assert_eq!(m.transform_point(v), Vector3f64::new(3.0, 2.0, 1.0));
+        assert_eq!(m.transform_vector(v), Vector3f64::new(2.0, 0.0, -2.0));
+
+        // Perspective divide puts the near plane at depth 0
+        let p = Matrix4x4f64::perspective(1.0, 1.0, 0.5, 10.0);
+        let projected = p.transform_point(Vector3f64::new(0.0, 0.0, -0.5));
+        assert!(projected[2].abs() < 1e-12);
+
+        let m = Matrix3x3f64::translation_2d(Vector2f64::new(5.0, -1.0))
+            * Matrix3x3f64::rotation_2d(core::f64::consts::FRAC_PI_2);
+        let p = m.transform_point(Vector2f64::new(1.0, 0.0));
+        let d = m.transform_vector(Vector2f64::new(1.0, 0.0));
+        assert!((p[0] - 5.0).abs() < 1e-12 && (p[1] - 0.0).abs() < 1e-12);
+        assert!(d[0].abs() < 1e-12 && (d[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_transform_normal() {
+        // Non-uniform scale: the transformed normal stays perpendicular to the surface
+        let m = Matrix4x4f64::rotation_z(0.3) * Matrix4x4f64::scale(Vector3f64::new(4.0, 1.0, 1.0));
+        let tangent = Vector3f64::new(1.0, -1.0, 0.0);
+        let normal = Vector3f64::new(1.0, 1.0, 0.0);
+        let n = m.transform_normal(normal).unwrap();
+        assert!(n.dot(&m.transform_vector(tangent)).abs() < 1e-12);
+
+        let m = Matrix3x3f64::scale_2d(Vector2f64::new(1.0, 3.0));
+        let n = m.transform_normal(Vector2f64::new(1.0, 1.0)).unwrap();
+        assert!((n[0] - 1.0).abs() < 1e-12 && (n[1] - 1.0 / 3.0).abs() < 1e-12);
+
+        let flat = Matrix4x4f64::scale(Vector3f64::new(1.0, 0.0, 1.0));
+        assert!(flat.transform_normal(normal).is_none());
+    }
 }
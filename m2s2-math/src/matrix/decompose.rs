@@ -0,0 +1,178 @@
+use core::array;
+
+use num_traits::Float;
+
+use crate::matrix::{Matrix3x3, Matrix4x4};
+use crate::quaternian::Quaternion;
+use crate::vector::Vector3;
+use crate::vector::vector_ops::Vector3Ops;
+
+/// Affine transform split into parts, `M = T * R * S * H` for column vectors.
+///
+/// `H` is the unit upper-triangular shear `[[1, xy, xz], [0, 1, yz], [0, 0, 1]]` with
+/// `shear = (xy, xz, yz)`. Mirroring transforms are reported as a negative z scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AffineParts<T> {
+    pub translation: Vector3<T>,
+    pub rotation: Quaternion<T>,
+    pub scale: Vector3<T>,
+    pub shear: Vector3<T>,
+}
+
+impl<T: Float> AffineParts<T> {
+    /// Rebuild the matrix, including shear
+    pub fn to_matrix(&self) -> Matrix4x4<T> {
+        let (s, h) = (self.scale, self.shear);
+        let zero = T::zero();
+        let stretch = Matrix3x3::from_2d_array([
+            [s[0], s[0] * h[0], s[0] * h[1]],
+            [zero, s[1], s[1] * h[2]],
+            [zero, zero, s[2]],
+        ]);
+        compose(self.translation, self.rotation.to_matrix3x3() * stretch)
+    }
+}
+
+// Affine 4x4 with `linear` in the upper-left block and `translation` in the last column
+pub(crate) fn compose<T: Float>(translation: Vector3<T>, linear: Matrix3x3<T>) -> Matrix4x4<T> {
+    Matrix4x4::from_2d_array(array::from_fn(|i| {
+        if i < 3 {
+            [linear[i][0], linear[i][1], linear[i][2], translation[i]]
+        } else {
+            [T::zero(), T::zero(), T::zero(), T::one()]
+        }
+    }))
+}
+
+impl<T: Float> Matrix4x4<T> {
+    /// Split an affine transform into translation, rotation, scale and shear.
+    ///
+    /// Returns `None` for projective matrices (last row not `[0, 0, 0, 1]`) and for
+    /// matrices that collapse an axis.
+    pub fn decompose(&self) -> Option<AffineParts<T>> {
+        let zero = T::zero();
+        if self[3][0] != zero || self[3][1] != zero || self[3][2] != zero || self[3][3] != T::one()
+        {
+            return None;
+        }
+
+        let column = |j: usize| Vector3::new(self[0][j], self[1][j], self[2][j]);
+        let (c0, c1, c2) = (column(0), column(1), column(2));
+        let tolerance = c0.length().max(c1.length()).max(c2.length()) * T::epsilon();
+
+        // Gram-Schmidt: the projections onto earlier axes are the scaled shear terms
+        let sx = c0.length();
+        if sx <= tolerance {
+            return None;
+        }
+        let x_axis = c0 * (T::one() / sx);
+
+        let xy = x_axis.dot(&c1);
+        let c1 = c1 - x_axis * xy;
+        let sy = c1.length();
+        if sy <= tolerance {
+            return None;
+        }
+        let y_axis = c1 * (T::one() / sy);
+
+        let xz = x_axis.dot(&c2);
+        let yz = y_axis.dot(&c2);
+        let c2 = c2 - x_axis * xz - y_axis * yz;
+        let mut sz = c2.length();
+        if sz <= tolerance {
+            return None;
+        }
+        let mut z_axis = c2 * (T::one() / sz);
+
+        // Keep the rotation proper by folding a reflection into the z scale
+        if x_axis.cross(&y_axis).dot(&z_axis) < zero {
+            z_axis = -z_axis;
+            sz = -sz;
+        }
+
+        let rotation =
+            Matrix3x3::from_2d_array(array::from_fn(|i| [x_axis[i], y_axis[i], z_axis[i]]));
+
+        Some(AffineParts {
+            translation: column(3),
+            rotation: Quaternion::from_matrix3x3(&rotation),
+            scale: Vector3::new(sx, sy, sz),
+            shear: Vector3::new(xy / sx, xz / sx, yz / sy),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AffineParts;
+    use crate::quaternian::Quaternion;
+    use crate::test_util::assert_matrix_eq;
+    use crate::vector::{Vector3f32, Vector3f64, Vector4f32};
+    use crate::{Matrix4x4f32, Matrix4x4f64, Transform4x4};
+
+    #[test]
+    fn test_decompose_trs() {
+        let translation = Vector3f64::new(1.0, -2.0, 3.0);
+        let rotation = Quaternion::from_axis_angle(Vector3f64::new(1.0, 2.0, -0.5), 0.9);
+        let scale = Vector3f64::new(2.0, 0.5, 3.0);
+        let m = Matrix4x4f64::from_trs(translation, rotation, scale);
+
+        let parts = m.decompose().unwrap();
+        assert_eq!(parts.translation, translation);
+        assert!(parts.rotation.dot(&rotation).abs() > 1.0 - 1e-12);
+        for i in 0..3 {
+            assert!((parts.scale[i] - scale[i]).abs() < 1e-12);
+            assert!(parts.shear[i].abs() < 1e-12);
+        }
+
+        // Same as composing the individual builders
+        let expected = Matrix4x4f64::translation(translation)
+            * rotation.to_matrix4x4()
+            * Matrix4x4f64::scale(scale);
+        assert_matrix_eq(&m, &expected, 1e-10);
+    }
+
+    #[test]
+    fn test_decompose_mirror_and_shear() {
+        let mirrored =
+            Matrix4x4f64::rotation_y(0.4) * Matrix4x4f64::scale(Vector3f64::new(-1.0, 2.0, 2.0));
+        let parts = mirrored.decompose().unwrap();
+        assert!(parts.scale[2] < 0.0);
+        assert!(parts.rotation.to_matrix3x3().determinant() > 0.0);
+        assert_matrix_eq(&parts.to_matrix(), &mirrored, 1e-10);
+
+        let parts = AffineParts {
+            translation: Vector3f64::new(4.0, 5.0, 6.0),
+            rotation: Quaternion::from_axis_angle(Vector3f64::new(0.0, 0.0, 1.0), 1.1),
+            scale: Vector3f64::new(1.5, 2.0, 0.5),
+            shear: Vector3f64::new(0.3, -0.2, 0.7),
+        };
+        let sheared = parts.to_matrix();
+        let decomposed = sheared.decompose().unwrap();
+        for i in 0..3 {
+            assert!((decomposed.scale[i] - parts.scale[i]).abs() < 1e-12);
+            assert!((decomposed.shear[i] - parts.shear[i]).abs() < 1e-12);
+        }
+        assert_matrix_eq(&decomposed.to_matrix(), &sheared, 1e-10);
+    }
+
+    #[test]
+    fn test_decompose_rejects_degenerate() {
+        let flat = Matrix4x4f64::scale(Vector3f64::new(1.0, 0.0, 1.0));
+        assert!(flat.decompose().is_none());
+
+        let projection = Matrix4x4f64::perspective(1.0, 1.0, 0.1, 10.0);
+        assert!(projection.decompose().is_none());
+    }
+
+    #[test]
+    fn test_from_trs_applies_scale_first() {
+        let m = Matrix4x4f32::from_trs(
+            Vector3f32::new(0.0, 1.0, 0.0),
+            Quaternion::identity(),
+            Vector3f32::new(2.0, 2.0, 2.0),
+        );
+        let p = m * Vector4f32::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(p, Vector4f32::new(2.0, 3.0, 2.0, 1.0));
+    }
+}
@@ -0,0 +1,444 @@
+use core::array;
+
+use num_traits::Float;
+
+use crate::matrix::Matrix;
+use crate::vector::Vector;
+
+/// LU decomposition with partial pivoting, `P * A = L * U`.
+///
+/// `L` (unit lower triangular) and `U` (upper triangular) are packed into one matrix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lu<T, const N: usize> {
+    lu: Matrix<T, N, N>,
+    permutation: [usize; N],
+    sign: T,
+    singular: bool,
+}
+
+/// Cholesky decomposition `A = L * L^T` of a symmetric positive-definite matrix.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cholesky<T, const N: usize> {
+    l: Matrix<T, N, N>,
+}
+
+/// Householder QR decomposition `A = Q * R` with `Q` orthogonal and `R` upper triangular.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Qr<T, const N: usize> {
+    q: Matrix<T, N, N>,
+    r: Matrix<T, N, N>,
+    tolerance: T,
+}
+
+// Pivots at or below this magnitude are treated as zero. Scaled by the largest entry so the
+// test does not depend on the units of the matrix.
+fn singular_tolerance<T: Float, const N: usize>(m: &Matrix<T, N, N>) -> T {
+    let max_abs = m
+        .as_slice()
+        .iter()
+        .fold(T::zero(), |acc, &val| acc.max(val.abs()));
+    let n = T::from(N).unwrap_or_else(T::one);
+    max_abs * n * T::epsilon()
+}
+
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    /// LU decomposition with partial pivoting
+    pub fn lu(&self) -> Lu<T, N> {
+        let tolerance = singular_tolerance(self);
+        let mut lu = *self;
+        let mut permutation: [usize; N] = array::from_fn(|i| i);
+        let mut sign = T::one();
+        let mut singular = false;
+
+        for k in 0..N {
+            let pivot_row = (k..N)
+                .max_by(|&a, &b| {
+                    lu.data[a][k]
+                        .abs()
+                        .partial_cmp(&lu.data[b][k].abs())
+                        .unwrap_or(core::cmp::Ordering::Equal)
+                })
+                .unwrap_or(k);
+            if pivot_row != k {
+                lu.data.swap(pivot_row, k);
+                permutation.swap(pivot_row, k);
+                sign = -sign;
+            }
+
+            let pivot = lu.data[k][k];
+            if pivot.abs() <= tolerance {
+                singular = true;
+                continue;
+            }
+
+            for i in (k + 1)..N {
+                let factor = lu.data[i][k] / pivot;
+                lu.data[i][k] = factor;
+                for j in (k + 1)..N {
+                    lu.data[i][j] = lu.data[i][j] - factor * lu.data[k][j];
+                }
+            }
+        }
+
+        Lu {
+            lu,
+            permutation,
+            sign,
+            singular,
+        }
+    }
+
+    /// Cholesky decomposition, or `None` if the matrix is not positive definite, including
+    /// when it is singular to within rounding.
+    /// Only the lower triangle is read; the matrix is assumed to be symmetric.
+    pub fn cholesky(&self) -> Option<Cholesky<T, N>> {
+        let tolerance = singular_tolerance(self);
+        let mut l = Matrix::from_2d_array([[T::zero(); N]; N]);
+
+        for j in 0..N {
+            let mut diag = self.data[j][j];
+            for k in 0..j {
+                diag = diag - l.data[j][k] * l.data[j][k];
+            }
+            if diag <= tolerance || !diag.is_finite() {
+                return None;
+            }
+            let diag = diag.sqrt();
+            l.data[j][j] = diag;
+
+            for i in (j + 1)..N {
+                let mut sum = self.data[i][j];
+                for k in 0..j {
+                    sum = sum - l.data[i][k] * l.data[j][k];
+                }
+                l.data[i][j] = sum / diag;
+            }
+        }
+
+        Some(Cholesky { l })
+    }
+
+    /// QR decomposition using Householder reflections
+    pub fn qr(&self) -> Qr<T, N> {
+        let two = T::one() + T::one();
+        let tolerance = singular_tolerance(self);
+        let mut q = Matrix::identity();
+        let mut r = *self;
+
+        for k in 0..N.saturating_sub(1) {
+            // Householder vector v that reflects column k below the diagonal onto e_k
+            let mut norm_sq = T::zero();
+            for i in k..N {
+                norm_sq = norm_sq + r.data[i][k] * r.data[i][k];
+            }
+            let norm = norm_sq.sqrt();
+            if norm == T::zero() {
+                continue;
+            }
+            let alpha = if r.data[k][k] > T::zero() {
+                -norm
+            } else {
+                norm
+            };
+
+            let mut v = [T::zero(); N];
+            for (i, vi) in v.iter_mut().enumerate().skip(k) {
+                *vi = r.data[i][k];
+            }
+            v[k] = v[k] - alpha;
+            let v_norm_sq = v[k..].iter().fold(T::zero(), |acc, &x| acc + x * x);
+            if v_norm_sq == T::zero() {
+                continue;
+            }
+
+            // R = H * R
+            for j in 0..N {
+                let mut dot = T::zero();
+                for (i, &vi) in v.iter().enumerate().skip(k) {
+                    dot = dot + vi * r.data[i][j];
+                }
+                let scale = two * dot / v_norm_sq;
+                for (i, &vi) in v.iter().enumerate().skip(k) {
+                    r.data[i][j] = r.data[i][j] - scale * vi;
+                }
+            }
+
+            // Q = Q * H
+            for i in 0..N {
+                let mut dot = T::zero();
+                for (j, &vj) in v.iter().enumerate().skip(k) {
+                    dot = dot + q.data[i][j] * vj;
+                }
+                let scale = two * dot / v_norm_sq;
+                for (j, &vj) in v.iter().enumerate().skip(k) {
+                    q.data[i][j] = q.data[i][j] - scale * vj;
+                }
+            }
+        }
+
+        // Clean the reflected-away entries below the diagonal
+        for i in 1..N {
+            for j in 0..i {
+                r.data[i][j] = T::zero();
+            }
+        }
+
+        Qr { q, r, tolerance }
+    }
+
+    /// Solve `A * x = b` using LU decomposition. Returns `None` if the matrix is singular.
+    pub fn solve(&self, b: Vector<T, N>) -> Option<Vector<T, N>> {
+        self.lu().solve(b)
+    }
+}
+
+// Solve U * x = y for upper triangular U
+fn back_substitute<T: Float, const N: usize>(u: &Matrix<T, N, N>, y: [T; N]) -> Vector<T, N> {
+    let mut x = y;
+    for i in (0..N).rev() {
+        let mut sum = x[i];
+        for (j, &xj) in x.iter().enumerate().skip(i + 1) {
+            sum = sum - u.data[i][j] * xj;
+        }
+        x[i] = sum / u.data[i][i];
+    }
+    Vector::from_array(x)
+}
+
+impl<T: Float, const N: usize> Lu<T, N> {
+    /// True if a zero pivot was found
+    pub fn is_singular(&self) -> bool {
+        self.singular
+    }
+
+    /// Unit lower triangular factor
+    pub fn l(&self) -> Matrix<T, N, N> {
+        Matrix::from_2d_array(array::from_fn(|i| {
+            array::from_fn(|j| match i.cmp(&j) {
+                core::cmp::Ordering::Greater => self.lu.data[i][j],
+                core::cmp::Ordering::Equal => T::one(),
+                core::cmp::Ordering::Less => T::zero(),
+            })
+        }))
+    }
+
+    /// Upper triangular factor
+    pub fn u(&self) -> Matrix<T, N, N> {
+        Matrix::from_2d_array(array::from_fn(|i| {
+            array::from_fn(|j| {
+                if i <= j {
+                    self.lu.data[i][j]
+                } else {
+                    T::zero()
+                }
+            })
+        }))
+    }
+
+    /// Row permutation: row `i` of `P * A` is row `permutation()[i]` of `A`
+    pub fn permutation(&self) -> [usize; N] {
+        self.permutation
+    }
+
+    pub fn determinant(&self) -> T {
+        if self.singular {
+            return T::zero();
+        }
+        (0..N).fold(self.sign, |acc, i| acc * self.lu.data[i][i])
+    }
+
+    /// Solve `A * x = b`. Returns `None` if the matrix is singular.
+    pub fn solve(&self, b: Vector<T, N>) -> Option<Vector<T, N>> {
+        if self.singular {
+            return None;
+        }
+        Some(self.substitute(b))
+    }
+
+    /// Inverse of the decomposed matrix, or `None` if it is singular
+    pub fn inverse(&self) -> Option<Matrix<T, N, N>> {
+        if self.singular {
+            return None;
+        }
+        let columns: [Vector<T, N>; N] = array::from_fn(|j| {
+            self.substitute(Vector::from_array(array::from_fn(|i| {
+                if i == j { T::one() } else { T::zero() }
+            })))
+        });
+        Some(Matrix::from_2d_array(array::from_fn(|i| {
+            array::from_fn(|j| columns[j][i])
+        })))
+    }
+}
+
+impl<T: Float, const N: usize> Lu<T, N> {
+    // Forward substitution with the unit lower triangle (L * y = P * b), then back substitution
+    fn substitute(&self, b: Vector<T, N>) -> Vector<T, N> {
+        let mut y: [T; N] = array::from_fn(|i| b[self.permutation[i]]);
+        for i in 0..N {
+            for j in 0..i {
+                y[i] = y[i] - self.lu.data[i][j] * y[j];
+            }
+        }
+        back_substitute(&self.lu, y)
+    }
+}
+
+impl<T: Float, const N: usize> Cholesky<T, N> {
+    /// Lower triangular factor
+    pub fn l(&self) -> Matrix<T, N, N> {
+        self.l
+    }
+
+    pub fn determinant(&self) -> T {
+        let diag = (0..N).fold(T::one(), |acc, i| acc * self.l.data[i][i]);
+        diag * diag
+    }
+
+    /// Solve `A * x = b`. Never fails, since `cholesky` rejects singular matrices.
+    pub fn solve(&self, b: Vector<T, N>) -> Vector<T, N> {
+        // L * y = b
+        let mut y: [T; N] = array::from_fn(|i| b[i]);
+        for i in 0..N {
+            for j in 0..i {
+                y[i] = y[i] - self.l.data[i][j] * y[j];
+            }
+            y[i] = y[i] / self.l.data[i][i];
+        }
+        // L^T * x = y
+        back_substitute(&self.l.transpose(), y)
+    }
+}
+
+impl<T: Float, const N: usize> Qr<T, N> {
+    /// Orthogonal factor
+    pub fn q(&self) -> Matrix<T, N, N> {
+        self.q
+    }
+
+    /// Upper triangular factor
+    pub fn r(&self) -> Matrix<T, N, N> {
+        self.r
+    }
+
+    /// True if `R` has a zero on its diagonal
+    pub fn is_singular(&self) -> bool {
+        (0..N).any(|i| self.r.data[i][i].abs() <= self.tolerance)
+    }
+
+    /// Solve `A * x = b`. Returns `None` if the matrix is singular.
+    pub fn solve(&self, b: Vector<T, N>) -> Option<Vector<T, N>> {
+        if self.is_singular() {
+            return None;
+        }
+        // R * x = Q^T * b
+        let y = self.q.transpose() * b;
+        Some(back_substitute(&self.r, array::from_fn(|i| y[i])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::assert_matrix_eq;
+    use crate::vector::{Vector2f64, Vector3f64, Vector4f64};
+    use crate::{Matrix2x2f64, Matrix3x3f64, Matrix4x4f64};
+
+    fn sample_4x4() -> Matrix4x4f64 {
+        Matrix4x4f64::from_2d_array([
+            [0.0, 2.0, 1.0, -1.0],
+            [3.0, 0.0, 0.0, 5.0],
+            [2.0, 1.0, 4.0, -3.0],
+            [1.0, 0.0, 5.0, 0.0],
+        ])
+    }
+
+    #[test]
+    fn test_lu_reconstructs_and_solves() {
+        let a = sample_4x4();
+        let lu = a.lu();
+        assert!(!lu.is_singular());
+
+        let p = lu.permutation();
+        let pa = Matrix4x4f64::from_2d_array(core::array::from_fn(|i| {
+            core::array::from_fn(|j| a[p[i]][j])
+        }));
+        assert_matrix_eq(&pa, &(lu.l() * lu.u()), 1e-10);
+        assert!((lu.determinant() - a.determinant()).abs() < 1e-10);
+
+        let x = Vector4f64::new(1.0, -2.0, 0.5, 3.0);
+        let solved = lu.solve(a * x).unwrap();
+        for i in 0..4 {
+            assert!((solved[i] - x[i]).abs() < 1e-10);
+        }
+        assert_matrix_eq(&lu.inverse().unwrap(), &a.inverse().unwrap(), 1e-10);
+    }
+
+    #[test]
+    fn test_lu_singular() {
+        let a = Matrix3x3f64::from_2d_array([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 0.0, 1.0]]);
+        let lu = a.lu();
+        assert!(lu.is_singular());
+        assert_eq!(lu.determinant(), 0.0);
+        assert_eq!(a.solve(Vector3f64::new(1.0, 2.0, 3.0)), None);
+        assert_eq!(lu.inverse(), None);
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = Matrix3x3f64::from_2d_array([
+            [4.0, 12.0, -16.0],
+            [12.0, 37.0, -43.0],
+            [-16.0, -43.0, 98.0],
+        ]);
+        let chol = a.cholesky().unwrap();
+        let l = chol.l();
+        assert_matrix_eq(
+            &l,
+            &Matrix3x3f64::from_2d_array([[2.0, 0.0, 0.0], [6.0, 1.0, 0.0], [-8.0, 5.0, 3.0]]),
+            1e-10,
+        );
+        assert_matrix_eq(&(l * l.transpose()), &a, 1e-10);
+        assert!((chol.determinant() - a.determinant()).abs() < 1e-8);
+
+        let x = Vector3f64::new(1.0, 2.0, 3.0);
+        let solved = chol.solve(a * x);
+        for i in 0..3 {
+            assert!((solved[i] - x[i]).abs() < 1e-10);
+        }
+
+        let indefinite =
+            Matrix3x3f64::from_2d_array([[1.0, 2.0, 0.0], [2.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(indefinite.cholesky(), None);
+
+        // Singular in exact arithmetic, with a tiny positive pivot after rounding
+        let near_singular = Matrix2x2f64::from_2d_array([[0.1, 0.3], [0.3, 0.9]]);
+        assert_eq!(near_singular.cholesky(), None);
+        assert_eq!(near_singular.lu().solve(Vector2f64::new(1.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_qr() {
+        let a = sample_4x4();
+        let qr = a.qr();
+        let q = qr.q();
+        let r = qr.r();
+
+        assert_matrix_eq(&(q * r), &a, 1e-10);
+        assert_matrix_eq(&(q.transpose() * q), &Matrix4x4f64::identity(), 1e-10);
+        for i in 1..4 {
+            for j in 0..i {
+                assert_eq!(r[i][j], 0.0);
+            }
+        }
+
+        let x = Vector4f64::new(-1.0, 0.25, 2.0, 1.5);
+        let solved = qr.solve(a * x).unwrap();
+        for i in 0..4 {
+            assert!((solved[i] - x[i]).abs() < 1e-10);
+        }
+
+        let singular =
+            Matrix3x3f64::from_2d_array([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 0.0, 1.0]]);
+        assert_eq!(singular.qr().solve(Vector3f64::new(1.0, 1.0, 1.0)), None);
+    }
+}
@@ -0,0 +1,343 @@
+use core::array;
+
+use num_traits::Float;
+
+use crate::matrix::Matrix;
+use crate::vector::Vector;
+
+// Upper bound on Jacobi sweeps. Convergence is quadratic, so small matrices need well
+// under ten sweeps; the limit only guards against NaN input.
+const MAX_SWEEPS: usize = 64;
+
+/// Eigen-decomposition `A = V * diag(eigenvalues) * V^T` of a symmetric matrix.
+///
+/// Eigenvalues are sorted in descending order and column `i` of `eigenvectors` is the unit
+/// eigenvector for eigenvalue `i`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SymmetricEigen<T, const N: usize> {
+    eigenvalues: Vector<T, N>,
+    eigenvectors: Matrix<T, N, N>,
+}
+
+/// Singular value decomposition `A = U * diag(singular_values) * V^T`.
+///
+/// Singular values are non-negative and sorted in descending order. `U` and `V` are orthonormal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Svd<T, const N: usize> {
+    u: Matrix<T, N, N>,
+    singular_values: Vector<T, N>,
+    v: Matrix<T, N, N>,
+}
+
+// Jacobi rotation (c, s) that zeroes the off-diagonal term of the 2x2 symmetric block
+// [[app, apq], [apq, aqq]]
+fn jacobi_rotation<T: Float>(app: T, aqq: T, apq: T) -> (T, T) {
+    let two = T::one() + T::one();
+    let theta = (aqq - app) / (two * apq);
+    let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+    let c = T::one() / (t * t + T::one()).sqrt();
+    (c, t * c)
+}
+
+// Rotate columns p and q of m by (c, s)
+fn rotate_columns<T: Float, const N: usize>(
+    m: &mut Matrix<T, N, N>,
+    p: usize,
+    q: usize,
+    c: T,
+    s: T,
+) {
+    for row in m.data.iter_mut() {
+        let (mp, mq) = (row[p], row[q]);
+        row[p] = c * mp - s * mq;
+        row[q] = s * mp + c * mq;
+    }
+}
+
+// Order of indices that sorts `values` in descending order
+fn descending_order<T: Float, const N: usize>(values: &[T; N]) -> [usize; N] {
+    let mut order: [usize; N] = array::from_fn(|i| i);
+    order.sort_unstable_by(|&a, &b| {
+        values[b]
+            .partial_cmp(&values[a])
+            .unwrap_or(core::cmp::Ordering::Equal)
+    });
+    order
+}
+
+fn permute_columns<T: Copy, const N: usize>(
+    m: &Matrix<T, N, N>,
+    order: &[usize; N],
+) -> Matrix<T, N, N> {
+    Matrix::from_2d_array(array::from_fn(|i| array::from_fn(|j| m.data[i][order[j]])))
+}
+
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    /// Eigenvalues and eigenvectors of a symmetric matrix using cyclic Jacobi rotations.
+    /// Only the symmetric part of the matrix is meaningful.
+    pub fn symmetric_eigen(&self) -> SymmetricEigen<T, N> {
+        let mut a = *self;
+        let mut v = Matrix::identity();
+        let scale = a.as_slice().iter().fold(T::zero(), |acc, &x| acc + x * x);
+        let tolerance = T::epsilon() * T::epsilon() * scale;
+
+        for _ in 0..MAX_SWEEPS {
+            let mut off_diagonal = T::zero();
+            for p in 0..N {
+                for q in (p + 1)..N {
+                    off_diagonal = off_diagonal + a.data[p][q] * a.data[p][q];
+                }
+            }
+            if off_diagonal <= tolerance || !off_diagonal.is_finite() {
+                break;
+            }
+
+            for p in 0..N {
+                for q in (p + 1)..N {
+                    if a.data[p][q] == T::zero() {
+                        continue;
+                    }
+                    let (c, s) = jacobi_rotation(a.data[p][p], a.data[q][q], a.data[p][q]);
+
+                    // A = J^T * A * J
+                    rotate_columns(&mut a, p, q, c, s);
+                    for k in 0..N {
+                        let (apk, aqk) = (a.data[p][k], a.data[q][k]);
+                        a.data[p][k] = c * apk - s * aqk;
+                        a.data[q][k] = s * apk + c * aqk;
+                    }
+                    rotate_columns(&mut v, p, q, c, s);
+                }
+            }
+        }
+
+        let values: [T; N] = array::from_fn(|i| a.data[i][i]);
+        let order = descending_order(&values);
+        SymmetricEigen {
+            eigenvalues: Vector::from_array(array::from_fn(|i| values[order[i]])),
+            eigenvectors: permute_columns(&v, &order),
+        }
+    }
+
+    /// Singular value decomposition using one-sided (Hestenes) Jacobi rotations
+    pub fn svd(&self) -> Svd<T, N> {
+        let mut u = *self;
+        let mut v = Matrix::identity();
+
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+            for p in 0..N {
+                for q in (p + 1)..N {
+                    let (mut alpha, mut beta, mut gamma) = (T::zero(), T::zero(), T::zero());
+                    for row in u.data.iter() {
+                        alpha = alpha + row[p] * row[p];
+                        beta = beta + row[q] * row[q];
+                        gamma = gamma + row[p] * row[q];
+                    }
+                    if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() || gamma == T::zero() {
+                        continue;
+                    }
+
+                    // Orthogonalize columns p and q of A * V
+                    let (c, s) = jacobi_rotation(alpha, beta, gamma);
+                    rotate_columns(&mut u, p, q, c, s);
+                    rotate_columns(&mut v, p, q, c, s);
+                    rotated = true;
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+
+        let values: [T; N] = array::from_fn(|j| {
+            u.data
+                .iter()
+                .fold(T::zero(), |acc, row| acc + row[j] * row[j])
+                .sqrt()
+        });
+        let order = descending_order(&values);
+        let mut u = permute_columns(&u, &order);
+        let v = permute_columns(&v, &order);
+        let singular_values: [T; N] = array::from_fn(|i| values[order[i]]);
+
+        let largest = singular_values.first().copied().unwrap_or_else(T::zero);
+        let threshold = largest * T::epsilon() * T::from(N).unwrap_or_else(T::one);
+        for (j, &sigma) in singular_values.iter().enumerate() {
+            if sigma > threshold {
+                for row in u.data.iter_mut() {
+                    row[j] = row[j] / sigma;
+                }
+            } else {
+                complete_orthonormal_column(&mut u, j);
+            }
+        }
+
+        Svd {
+            u,
+            singular_values: Vector::from_array(singular_values),
+            v,
+        }
+    }
+}
+
+// Replace column `j` of `m` with a unit vector orthogonal to columns 0..j, found by
+// Gram-Schmidt on the standard basis. Used when a singular value is zero.
+fn complete_orthonormal_column<T: Float, const N: usize>(m: &mut Matrix<T, N, N>, j: usize) {
+    let mut best = [T::zero(); N];
+    let mut best_norm = T::zero();
+    for e in 0..N {
+        let mut candidate: [T; N] = array::from_fn(|i| if i == e { T::one() } else { T::zero() });
+        for k in 0..j {
+            let dot = (0..N).fold(T::zero(), |acc, i| acc + m.data[i][k] * candidate[i]);
+            for (i, c) in candidate.iter_mut().enumerate() {
+                *c = *c - dot * m.data[i][k];
+            }
+        }
+        let norm = candidate
+            .iter()
+            .fold(T::zero(), |acc, &x| acc + x * x)
+            .sqrt();
+        if norm > best_norm {
+            best_norm = norm;
+            best = candidate;
+        }
+    }
+    for (i, row) in m.data.iter_mut().enumerate() {
+        row[j] = best[i] / best_norm;
+    }
+}
+
+impl<T: Float, const N: usize> SymmetricEigen<T, N> {
+    /// Eigenvalues in descending order
+    pub fn eigenvalues(&self) -> Vector<T, N> {
+        self.eigenvalues
+    }
+
+    /// Unit eigenvectors as columns, in the same order as the eigenvalues
+    pub fn eigenvectors(&self) -> Matrix<T, N, N> {
+        self.eigenvectors
+    }
+
+    /// Rebuild `V * diag(eigenvalues) * V^T`
+    pub fn recompose(&self) -> Matrix<T, N, N> {
+        let scaled = Matrix::from_2d_array(array::from_fn(|i| {
+            array::from_fn(|j| self.eigenvectors.data[i][j] * self.eigenvalues[j])
+        }));
+        scaled * self.eigenvectors.transpose()
+    }
+}
+
+impl<T: Float, const N: usize> Svd<T, N> {
+    /// Left singular vectors as columns
+    pub fn u(&self) -> Matrix<T, N, N> {
+        self.u
+    }
+
+    /// Singular values in descending order
+    pub fn singular_values(&self) -> Vector<T, N> {
+        self.singular_values
+    }
+
+    /// Right singular vectors as columns
+    pub fn v(&self) -> Matrix<T, N, N> {
+        self.v
+    }
+
+    /// Rebuild `U * diag(singular_values) * V^T`
+    pub fn recompose(&self) -> Matrix<T, N, N> {
+        let scaled = Matrix::from_2d_array(array::from_fn(|i| {
+            array::from_fn(|j| self.u.data[i][j] * self.singular_values[j])
+        }));
+        scaled * self.v.transpose()
+    }
+
+    /// Number of singular values above `tolerance`
+    pub fn rank(&self, tolerance: T) -> usize {
+        self.singular_values
+            .as_slice()
+            .iter()
+            .filter(|&&sigma| sigma > tolerance)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::assert_matrix_eq;
+    use crate::{Matrix2x2f32, Matrix3x3f64, Matrix4x4f64};
+
+    #[test]
+    fn test_symmetric_eigen_3x3() {
+        let a =
+            Matrix3x3f64::from_2d_array([[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]]);
+        let eigen = a.symmetric_eigen();
+
+        let sqrt2 = 2.0f64.sqrt();
+        let expected = [2.0 + sqrt2, 2.0, 2.0 - sqrt2];
+        for (i, e) in expected.iter().enumerate() {
+            assert!((eigen.eigenvalues()[i] - e).abs() < 1e-12);
+        }
+
+        let v = eigen.eigenvectors();
+        assert_matrix_eq(&(v.transpose() * v), &Matrix3x3f64::identity(), 1e-12);
+        assert_matrix_eq(&eigen.recompose(), &a, 1e-12);
+    }
+
+    #[test]
+    fn test_symmetric_eigen_4x4_and_f32() {
+        let a = Matrix4x4f64::from_2d_array([
+            [4.0, 1.0, -2.0, 2.0],
+            [1.0, 2.0, 0.0, 1.0],
+            [-2.0, 0.0, 3.0, -2.0],
+            [2.0, 1.0, -2.0, -1.0],
+        ]);
+        let eigen = a.symmetric_eigen();
+        assert_matrix_eq(&eigen.recompose(), &a, 1e-10);
+        for i in 1..4 {
+            assert!(eigen.eigenvalues()[i - 1] >= eigen.eigenvalues()[i]);
+        }
+
+        let b = Matrix2x2f32::from_2d_array([[3.0, 1.0], [1.0, 3.0]]);
+        let eigen = b.symmetric_eigen();
+        assert!((eigen.eigenvalues()[0] - 4.0).abs() < 1e-6);
+        assert!((eigen.eigenvalues()[1] - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_svd() {
+        let a = Matrix3x3f64::from_2d_array([[3.0, 2.0, 2.0], [2.0, 3.0, -2.0], [1.0, 0.0, 4.0]]);
+        let svd = a.svd();
+
+        assert_matrix_eq(&svd.recompose(), &a, 1e-10);
+        assert_matrix_eq(
+            &(svd.u().transpose() * svd.u()),
+            &Matrix3x3f64::identity(),
+            1e-12,
+        );
+        assert_matrix_eq(
+            &(svd.v().transpose() * svd.v()),
+            &Matrix3x3f64::identity(),
+            1e-12,
+        );
+        for i in 1..3 {
+            assert!(svd.singular_values()[i - 1] >= svd.singular_values()[i]);
+        }
+        let product: f64 = svd.singular_values().as_slice().iter().product();
+        assert!((product - a.determinant().abs()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_svd_rank_deficient() {
+        let a = Matrix3x3f64::from_2d_array([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [1.0, 1.0, 1.0]]);
+        let svd = a.svd();
+
+        assert_eq!(svd.rank(1e-10), 2);
+        assert_matrix_eq(&svd.recompose(), &a, 1e-10);
+        assert_matrix_eq(
+            &(svd.u().transpose() * svd.u()),
+            &Matrix3x3f64::identity(),
+            1e-10,
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use num_traits::Float;
+
+use crate::matrix::Matrix;
+
+// Iteration limit for the Denman-Beavers square root. Quadratic convergence means
+// well-conditioned input finishes in under 20 iterations.
+const MAX_SQRT_ITERATIONS: usize = 100;
+
+// Terms of the atanh series used by `log`. After the square-root scaling the series
+// argument has norm below ~0.15, so this is far more than double precision needs.
+const MAX_LOG_TERMS: usize = 40;
+
+// Padé approximant degree for `exp`
+const EXP_PADE_DEGREE: usize = 6;
+
+impl<T: Float, const N: usize> Matrix<T, N, N> {
+    /// Matrix exponential using a diagonal Padé approximant with scaling and squaring
+    pub fn exp(&self) -> Self {
+        let one = T::one();
+        let two = one + one;
+
+        // Scale so that ||A / 2^s|| <= 1/2
+        let norm = self.norm_inf();
+        let mut squarings = 0;
+        let mut scale = one;
+        while norm * scale > one / two {
+            scale = scale / two;
+            squarings += 1;
+        }
+        let a = *self * scale;
+
+        let identity = Matrix::identity();
+        let mut term = a;
+        let mut c = one / two;
+        let mut numerator = identity + a * c;
+        let mut denominator = identity - a * c;
+        for k in 2..=EXP_PADE_DEGREE {
+            let kf = T::from(k).unwrap_or(one);
+            let q = T::from(EXP_PADE_DEGREE).unwrap_or(one);
+            c = c * (q - kf + one) / (kf * (two * q - kf + one));
+            term = a * term;
+            numerator = numerator + term * c;
+            denominator = if k % 2 == 0 {
+                denominator + term * c
+            } else {
+                denominator - term * c
+            };
+        }
+
+        // The denominator is close to the identity after scaling, so it is never singular
+        // for finite input
+        let mut result = match denominator.lu().inverse() {
+            Some(inv) => inv * numerator,
+            None => return Matrix::from_2d_array([[T::nan(); N]; N]),
+        };
+        for _ in 0..squarings {
+            result = result * result;
+        }
+        result
+    }
+
+    /// Principal square root using the Denman-Beavers iteration.
+    ///
+    /// Returns `None` for singular matrices and matrices with eigenvalues on the closed
+    /// negative real axis, which have no real principal square root.
+    pub fn sqrt(&self) -> Option<Self> {
+        let half = T::one() / (T::one() + T::one());
+        let tolerance = T::epsilon() * T::from(N).unwrap_or_else(T::one) * self.norm_inf();
+
+        let mut y = *self;
+        let mut z = Matrix::identity();
+        for _ in 0..MAX_SQRT_ITERATIONS {
+            let y_inv = y.lu().inverse()?;
+            let z_inv = z.lu().inverse()?;
+            let next_y = (y + z_inv) * half;
+            z = (z + y_inv) * half;
+
+            let change = (next_y - y).norm_inf();
+            y = next_y;
+            if !change.is_finite() {
+                return None;
+            }
+            if change <= tolerance {
+                return Some(y);
+            }
+        }
+        None
+    }
+
+    /// Principal logarithm using inverse scaling and squaring.
+    ///
+    /// Returns `None` when no real principal logarithm exists (singular matrices or
+    /// eigenvalues on the negative real axis).
+    pub fn log(&self) -> Option<Self> {
+        let one = T::one();
+        let two = one + one;
+        let quarter = one / (two * two);
+        let identity = Matrix::identity();
+
+        // Take square roots until A is close to the identity: log(A) = 2^k log(A^(1/2^k))
+        let mut a = *self;
+        let mut scale = one;
+        while (a - identity).norm_inf() > quarter {
+            a = a.sqrt()?;
+            scale = scale * two;
+            if scale > T::from(1u64 << 40).unwrap_or_else(T::max_value) {
+                return None;
+            }
+        }
+
+        // log(A) = 2 * atanh(Z) = 2 * (Z + Z^3/3 + Z^5/5 + ...) with Z = (A - I)(A + I)^-1
+        let z = (a - identity) * (a + identity).lu().inverse()?;
+        let z_squared = z * z;
+        let mut power = z;
+        let mut sum = z;
+        for k in 1..MAX_LOG_TERMS {
+            power = power * z_squared;
+            let term = power * (one / T::from(2 * k + 1).unwrap_or(one));
+            sum = sum + term;
+            if term.norm_inf() <= T::epsilon() * sum.norm_inf() {
+                break;
+            }
+        }
+
+        Some(sum * (two * scale))
+    }
+
+    // Maximum absolute row sum
+    fn norm_inf(&self) -> T {
+        self.data.iter().fold(T::zero(), |max, row| {
+            max.max(row.iter().fold(T::zero(), |acc, &x| acc + x.abs()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_util::assert_matrix_eq;
+    use crate::vector::Vector3f64;
+    use crate::{Matrix3x3f64, Matrix4x4f64, Quaternionf64, Transform4x4};
+
+    fn skew(v: Vector3f64) -> Matrix3x3f64 {
+        Matrix3x3f64::from_2d_array([[0.0, -v[2], v[1]], [v[2], 0.0, -v[0]], [-v[1], v[0], 0.0]])
+    }
+
+    #[test]
+    fn test_exp_of_zero_and_diagonal() {
+        assert_eq!(
+            Matrix3x3f64::from_2d_array([[0.0; 3]; 3]).exp(),
+            Matrix3x3f64::identity()
+        );
+        let diag =
+            Matrix3x3f64::from_2d_array([[1.0, 0.0, 0.0], [0.0, -2.0, 0.0], [0.0, 0.0, 3.5]]);
+        let expected = Matrix3x3f64::from_2d_array([
+            [1.0f64.exp(), 0.0, 0.0],
+            [0.0, (-2.0f64).exp(), 0.0],
+            [0.0, 0.0, 3.5f64.exp()],
+        ]);
+        assert_matrix_eq(&diag.exp(), &expected, 1e-10);
+    }
+
+    #[test]
+    fn test_exp_of_skew_is_rotation() {
+        // Rodrigues: exp([w]x) rotates by |w| around w
+        let axis = Vector3f64::new(0.3, -0.5, 0.8);
+        let angle = 2.0;
+        let length = (0.3f64 * 0.3 + 0.5 * 0.5 + 0.8 * 0.8).sqrt();
+        let rotation = skew(axis * (angle / length)).exp();
+        let expected = Quaternionf64::from_axis_angle(axis, angle).to_matrix3x3();
+        assert_matrix_eq(&rotation, &expected, 1e-12);
+
+        // and log recovers the generator
+        assert_matrix_eq(
+            &rotation.log().unwrap(),
+            &skew(axis * (angle / length)),
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn test_exp_log_round_trip_4x4() {
+        let transform = Matrix4x4f64::translation(Vector3f64::new(1.0, 2.0, -3.0))
+            * Matrix4x4f64::rotation_axis_angle(Vector3f64::new(0.0, 1.0, 1.0), 0.8)
+            * Matrix4x4f64::uniform_scale(1.5);
+        let log = transform.log().unwrap();
+        assert_matrix_eq(&log.exp(), &transform, 1e-10);
+
+        // Half of the log is the "halfway" transform
+        let half = (log * 0.5).exp();
+        assert_matrix_eq(&(half * half), &transform, 1e-10);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        let a = Matrix3x3f64::from_2d_array([[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        let root = a.sqrt().unwrap();
+        assert_matrix_eq(&(root * root), &a, 1e-12);
+
+        let singular =
+            Matrix3x3f64::from_2d_array([[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(singular.sqrt(), None);
+        assert_eq!(singular.log(), None);
+
+        let negative =
+            Matrix3x3f64::from_2d_array([[-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+        assert_eq!(negative.sqrt(), None);
+    }
+}
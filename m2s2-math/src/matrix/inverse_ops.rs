@@ -0,0 +1,163 @@
+use core::array;
+
+use num_traits::{Float, Num};
+
+use crate::matrix::{Matrix, Matrix2x2, Matrix3x3, Matrix4x4};
+
+impl<T: Copy, const R: usize, const C: usize> Matrix<T, R, C> {
+    pub fn transpose(&self) -> Matrix<T, C, R> {
+        Matrix {
+            data: array::from_fn(|i| array::from_fn(|j| self.data[j][i])),
+        }
+    }
+}
+
+macro_rules! impl_matrix_inverse {
+    ($name:ident) => {
+        impl<T: Float> $name<T> {
+            /// Inverse via the adjugate, or `None` if the matrix is singular
+            pub fn inverse(&self) -> Option<Self> {
+                let det = self.determinant();
+                if det == T::zero() || !det.is_finite() {
+                    return None;
+                }
+                Some(self.adjugate() * (T::one() / det))
+            }
+        }
+    };
+}
+
+impl_matrix_inverse!(Matrix2x2);
+impl_matrix_inverse!(Matrix3x3);
+impl_matrix_inverse!(Matrix4x4);
+
+impl<T: Num + Copy> Matrix2x2<T> {
+    pub fn determinant(&self) -> T {
+        self[0][0] * self[1][1] - self[0][1] * self[1][0]
+    }
+
+    /// Transpose of the cofactor matrix
+    pub fn adjugate(&self) -> Self {
+        Matrix2x2::from_2d_array([
+            [self[1][1], T::zero() - self[0][1]],
+            [T::zero() - self[1][0], self[0][0]],
+        ])
+    }
+}
+
+impl<T: Num + Copy> Matrix3x3<T> {
+    pub fn determinant(&self) -> T {
+        let m = self;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// Transpose of the cofactor matrix
+    pub fn adjugate(&self) -> Self {
+        let m = self;
+        Matrix3x3::from_2d_array([
+            [
+                m[1][1] * m[2][2] - m[1][2] * m[2][1],
+                m[0][2] * m[2][1] - m[0][1] * m[2][2],
+                m[0][1] * m[1][2] - m[0][2] * m[1][1],
+            ],
+            [
+                m[1][2] * m[2][0] - m[1][0] * m[2][2],
+                m[0][0] * m[2][2] - m[0][2] * m[2][0],
+                m[0][2] * m[1][0] - m[0][0] * m[1][2],
+            ],
+            [
+                m[1][0] * m[2][1] - m[1][1] * m[2][0],
+                m[0][1] * m[2][0] - m[0][0] * m[2][1],
+                m[0][0] * m[1][1] - m[0][1] * m[1][0],
+            ],
+        ])
+    }
+}
+
+// 2x2 sub-determinants of the top two rows (s) and bottom two rows (c) of a 4x4 matrix,
+// shared by the determinant and adjugate (Laplace expansion).
+fn sub_determinants_4x4<T: Num + Copy>(m: &Matrix4x4<T>) -> ([T; 6], [T; 6]) {
+    let s = [
+        m[0][0] * m[1][1] - m[1][0] * m[0][1],
+        m[0][0] * m[1][2] - m[1][0] * m[0][2],
+        m[0][0] * m[1][3] - m[1][0] * m[0][3],
+        m[0][1] * m[1][2] - m[1][1] * m[0][2],
+        m[0][1] * m[1][3] - m[1][1] * m[0][3],
+        m[0][2] * m[1][3] - m[1][2] * m[0][3],
+    ];
+    let c = [
+        m[2][0] * m[3][1] - m[3][0] * m[2][1],
+        m[2][0] * m[3][2] - m[3][0] * m[2][2],
+        m[2][0] * m[3][3] - m[3][0] * m[2][3],
+        m[2][1] * m[3][2] - m[3][1] * m[2][2],
+        m[2][1] * m[3][3] - m[3][1] * m[2][3],
+        m[2][2] * m[3][3] - m[3][2] * m[2][3],
+    ];
+    (s, c)
+}
+
+impl<T: Num + Copy> Matrix4x4<T> {
+    pub fn determinant(&self) -> T {
+        let (s, c) = sub_determinants_4x4(self);
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    /// Transpose of the cofactor matrix
+    pub fn adjugate(&self) -> Self {
+        let m = self;
+        let (s, c) = sub_determinants_4x4(self);
+        Matrix4x4::from_2d_array([
+            [
+                m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3],
+                m[0][2] * c[4] - m[0][1] * c[5] - m[0][3] * c[3],
+                m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3],
+                m[2][2] * s[4] - m[2][1] * s[5] - m[2][3] * s[3],
+            ],
+            [
+                m[1][2] * c[2] - m[1][0] * c[5] - m[1][3] * c[1],
+                m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1],
+                m[3][2] * s[2] - m[3][0] * s[5] - m[3][3] * s[1],
+                m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1],
+            ],
+            [
+                m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0],
+                m[0][1] * c[2] - m[0][0] * c[4] - m[0][3] * c[0],
+                m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0],
+                m[2][1] * s[2] - m[2][0] * s[4] - m[2][3] * s[0],
+            ],
+            [
+                m[1][1] * c[1] - m[1][0] * c[3] - m[1][2] * c[0],
+                m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0],
+                m[3][1] * s[1] - m[3][0] * s[3] - m[3][2] * s[0],
+                m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0],
+            ],
+        ])
+    }
+}
+
+impl<T: Float> Matrix4x4<T> {
+    /// Fast inverse for affine transforms (bottom row `[0, 0, 0, 1]`, translation in the
+    /// last column). Only the upper-left 3x3 block is inverted. Returns `None` if that block
+    /// is singular.
+    pub fn inverse_affine(&self) -> Option<Self> {
+        let m = self;
+        let linear = Matrix3x3::from_2d_array([
+            [m[0][0], m[0][1], m[0][2]],
+            [m[1][0], m[1][1], m[1][2]],
+            [m[2][0], m[2][1], m[2][2]],
+        ]);
+        let inv = linear.inverse()?;
+        let t = [m[0][3], m[1][3], m[2][3]];
+
+        let mut result = Matrix4x4::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                result[i][j] = inv[i][j];
+            }
+            result[i][3] = -(inv[i][0] * t[0] + inv[i][1] * t[1] + inv[i][2] * t[2]);
+        }
+        Some(result)
+    }
+}
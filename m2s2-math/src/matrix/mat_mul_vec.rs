@@ -1,44 +1,66 @@
-use std::{
-    mem::MaybeUninit,
+use core::{
+    array,
     ops::{Add, Mul},
-    ptr,
 };
 
-use num_traits::Zero;
-
-use crate::vector::{Vector2, Vector3, Vector4};
-
-use super::{Matrix2x2, Matrix3x3, Matrix4x4};
-
-macro_rules! impl_matrix_mul_vector {
-    ($name:ident, $vec_type:ident, $mat_rows:expr, $mat_cols:expr, $mat_size:expr, $vec_d:expr) => {
-        //assert_eq!($mat_cols, $vec_d);
-        //assert_eq!($mat_rows, $vec_d);
-        impl<T: Add<Output = T> + Mul<Output = T> + Copy + Zero> Mul<$vec_type<T>> for $name<T>
-        where
-            [(); $mat_size]:,
-            [(); $mat_rows]:,
-            [(); $mat_cols]:,
-        {
-            type Output = $vec_type<T>;
-            fn mul(self, rhs: $vec_type<T>) -> Self::Output {
-                let mut res_data: MaybeUninit<[T; $vec_d]> = MaybeUninit::uninit();
-                let res_ptr = res_data.as_mut_ptr() as *mut T;
-                for i in 0..$mat_rows {
-                    let mut sum = T::zero();
-                    for j in 0..$mat_cols {
-                        sum = sum + self.data[i * $mat_cols + j] * rhs[j]
-                    }
-
-                    unsafe { ptr::write(res_ptr.add(i), sum) };
-                }
-
-                $vec_type::from_slice(&unsafe { res_data.assume_init() })
+use num_traits::{Float, Zero};
+
+use crate::vector::{Vector, Vector2, Vector3};
+
+use super::{Matrix, Matrix2x2, Matrix3x3, Matrix4x4};
+
+/// `(R x C) * Vector<C> = Vector<R>`, treating the vector as a column.
+impl<T, const R: usize, const C: usize> Mul<Vector<T, C>> for Matrix<T, R, C>
+where
+    T: Add<Output = T> + Mul<Output = T> + Copy + Zero,
+{
+    type Output = Vector<T, R>;
+    fn mul(self, rhs: Vector<T, C>) -> Self::Output {
+        Vector::from_array(array::from_fn(|i| {
+            let mut sum = T::zero();
+            for j in 0..C {
+                sum = sum + self.data[i][j] * rhs[j]
+            }
+            sum
+        }))
+    }
+}
+
+// Point, direction and normal transforms for homogeneous matrices acting on the vector
+// one dimension down
+macro_rules! impl_transform_vectors {
+    ($matrix:ident, $linear:ident, $vector:ident, $dim:expr) => {
+        impl<T: Float> $matrix<T> {
+            /// Transform a point (w = 1), dividing by the resulting w. Projective matrices
+            /// give non-finite results for points on the w = 0 plane.
+            pub fn transform_point(&self, point: $vector<T>) -> $vector<T> {
+                let row = |i: usize| {
+                    (0..$dim).fold(self.data[i][$dim], |sum, j| {
+                        sum + self.data[i][j] * point[j]
+                    })
+                };
+                let w = row($dim);
+                Vector::from_array(array::from_fn(|i| row(i) / w))
+            }
+
+            /// Transform a direction (w = 0), ignoring translation
+            pub fn transform_vector(&self, vector: $vector<T>) -> $vector<T> {
+                self.linear_part() * vector
+            }
+
+            /// Transform a surface normal by the inverse transpose of the linear part, so it
+            /// stays perpendicular under non-uniform scale. The result is not normalized.
+            /// Returns `None` when the linear part is singular.
+            pub fn transform_normal(&self, normal: $vector<T>) -> Option<$vector<T>> {
+                Some(self.linear_part().inverse()?.transpose() * normal)
+            }
+
+            fn linear_part(&self) -> $linear<T> {
+                Matrix::from_2d_array(array::from_fn(|i| array::from_fn(|j| self.data[i][j])))
             }
         }
     };
 }
 
-impl_matrix_mul_vector!(Matrix2x2, Vector2, 2, 2, 4, 2);
-impl_matrix_mul_vector!(Matrix3x3, Vector3, 3, 3, 9, 3);
-impl_matrix_mul_vector!(Matrix4x4, Vector4, 4, 4, 16, 4);
+impl_transform_vectors!(Matrix3x3, Matrix2x2, Vector2, 2);
+impl_transform_vectors!(Matrix4x4, Matrix3x3, Vector3, 3);
@@ -1,40 +1,194 @@
-use crate::vector::{Vector3, Vector4};
-use num_traits::{Float, Zero, One};
+use crate::quaternian::Quaternion;
+use crate::vector::Vector3;
+use num_traits::Float;
+
+/// Whether matrices multiply column vectors on the right (`M * v`) or row vectors on the
+/// left (`v * M`). Row-vector matrices are the transpose of column-vector ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VectorConvention {
+    #[default]
+    Column,
+    Row,
+}
+
+/// Handedness of view space. Right-handed cameras look down -Z, left-handed ones down +Z.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Handedness {
+    #[default]
+    Right,
+    Left,
+}
+
+/// Clip-space depth range produced by the projection builders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DepthRange {
+    /// Near maps to 0 and far to 1 (Vulkan, Direct3D, Metal, WebGPU)
+    #[default]
+    ZeroToOne,
+    /// Near maps to -1 and far to 1 (OpenGL)
+    NegativeOneToOne,
+}
+
+/// Convention used by the `Transform4x4` view and projection builders.
+///
+/// The default is right-handed with column vectors and a [0, 1] depth range. With
+/// `reverse_z` set, near and far swap ends of the depth range for better float precision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Convention {
+    pub vectors: VectorConvention,
+    pub handedness: Handedness,
+    pub depth: DepthRange,
+    pub reverse_z: bool,
+}
+
+impl Convention {
+    /// Right-handed, column vectors, [0, 1] depth (Vulkan, glTF)
+    pub const RIGHT_HANDED_COLUMN: Convention =
+        Convention::new(VectorConvention::Column, Handedness::Right);
+    /// Left-handed, row vectors, [0, 1] depth (Direct3D / DirectXMath)
+    pub const LEFT_HANDED_ROW: Convention =
+        Convention::new(VectorConvention::Row, Handedness::Left);
+    /// Right-handed, column vectors, [-1, 1] depth
+    pub const OPENGL: Convention =
+        Convention::RIGHT_HANDED_COLUMN.with_depth_range(DepthRange::NegativeOneToOne);
+
+    /// Convention with a [0, 1] depth range and no reverse-Z
+    pub const fn new(vectors: VectorConvention, handedness: Handedness) -> Self {
+        Convention {
+            vectors,
+            handedness,
+            depth: DepthRange::ZeroToOne,
+            reverse_z: false,
+        }
+    }
+
+    pub const fn with_depth_range(self, depth: DepthRange) -> Self {
+        Convention { depth, ..self }
+    }
+
+    pub const fn with_reverse_z(self, reverse_z: bool) -> Self {
+        Convention { reverse_z, ..self }
+    }
+}
 
 /// Trait for 4x4 transformation matrices
-pub trait Transform4x4<T> 
-where 
+///
+/// Matrices are stored row-major. Unless a `Convention` says otherwise, builders produce
+/// matrices for column vectors (`M * v`, translation in the last column) in a right-handed
+/// coordinate system, so `a * b` applies `b` first.
+pub trait Transform4x4<T>: Sized
+where
     T: Float + Copy,
 {
     /// Create a perspective projection matrix
-    fn perspective(fov_y_radians: T, aspect_ratio: T, near: T, far: T) -> Self;
-    
+    fn perspective(fov_y_radians: T, aspect_ratio: T, near: T, far: T) -> Self {
+        Self::perspective_with(
+            fov_y_radians,
+            aspect_ratio,
+            near,
+            far,
+            Convention::default(),
+        )
+    }
+
+    /// Create a perspective projection matrix for the given convention
+    fn perspective_with(
+        fov_y_radians: T,
+        aspect_ratio: T,
+        near: T,
+        far: T,
+        convention: Convention,
+    ) -> Self;
+
+    /// Create a perspective projection matrix with the far plane at infinity
+    fn perspective_infinite(fov_y_radians: T, aspect_ratio: T, near: T) -> Self {
+        Self::perspective_infinite_with(fov_y_radians, aspect_ratio, near, Convention::default())
+    }
+
+    /// Create an infinite-far perspective projection matrix for the given convention
+    fn perspective_infinite_with(
+        fov_y_radians: T,
+        aspect_ratio: T,
+        near: T,
+        convention: Convention,
+    ) -> Self;
+
+    /// Create an off-center perspective projection from the near-plane rectangle
+    fn frustum(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        Self::frustum_with(left, right, bottom, top, near, far, Convention::default())
+    }
+
+    /// Create an off-center perspective projection for the given convention
+    #[allow(clippy::too_many_arguments)]
+    fn frustum_with(
+        left: T,
+        right: T,
+        bottom: T,
+        top: T,
+        near: T,
+        far: T,
+        convention: Convention,
+    ) -> Self;
+
     /// Create a look-at view matrix
-    fn look_at(eye: Vector3<T>, target: Vector3<T>, up: Vector3<T>) -> Self;
-    
+    fn look_at(eye: Vector3<T>, target: Vector3<T>, up: Vector3<T>) -> Self {
+        Self::look_at_with(eye, target, up, Convention::default())
+    }
+
+    /// Create a look-at view matrix for the given convention
+    fn look_at_with(
+        eye: Vector3<T>,
+        target: Vector3<T>,
+        up: Vector3<T>,
+        convention: Convention,
+    ) -> Self;
+
     /// Create a translation matrix
     fn translation(translation: Vector3<T>) -> Self;
-    
+
     /// Create a uniform scale matrix
     fn uniform_scale(scale: T) -> Self;
-    
+
     /// Create a non-uniform scale matrix
     fn scale(scale: Vector3<T>) -> Self;
-    
+
+    /// Create a matrix that scales, then rotates, then translates. Inverse of
+    /// `Matrix4x4::decompose` for matrices without shear.
+    fn from_trs(translation: Vector3<T>, rotation: Quaternion<T>, scale: Vector3<T>) -> Self;
+
     /// Create rotation matrix around X axis
     fn rotation_x(angle_radians: T) -> Self;
-    
+
     /// Create rotation matrix around Y axis
     fn rotation_y(angle_radians: T) -> Self;
-    
+
     /// Create rotation matrix around Z axis
     fn rotation_z(angle_radians: T) -> Self;
-    
+
     /// Create rotation matrix around arbitrary axis
     fn rotation_axis_angle(axis: Vector3<T>, angle_radians: T) -> Self;
-    
-    /// Create orthographic projection matrix
-    fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self;
+
+    /// Create orthographic projection matrix. Unlike the other builders this keeps the
+    /// OpenGL [-1, 1] depth range (`Convention::OPENGL`) it has always produced.
+    fn orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> Self {
+        Self::orthographic_with(left, right, bottom, top, near, far, Convention::OPENGL)
+    }
+
+    /// Create orthographic projection matrix for the given convention
+    #[allow(clippy::too_many_arguments)]
+    fn orthographic_with(
+        left: T,
+        right: T,
+        bottom: T,
+        top: T,
+        near: T,
+        far: T,
+        convention: Convention,
+    ) -> Self;
+
+    /// Convert a column-vector matrix (as produced by the builders) to the given vector
+    /// convention
+    fn into_convention(self, vectors: VectorConvention) -> Self;
 }
 
 /// Trait for 3x3 transformation matrices (2D + homogeneous)
@@ -44,13 +198,13 @@ where
 {
     /// Create 2D translation matrix
     fn translation_2d(translation: crate::vector::Vector2<T>) -> Self;
-    
+
     /// Create 2D rotation matrix
     fn rotation_2d(angle_radians: T) -> Self;
-    
+
     /// Create 2D scale matrix
     fn scale_2d(scale: crate::vector::Vector2<T>) -> Self;
-    
+
     /// Create 2D uniform scale matrix
     fn uniform_scale_2d(scale: T) -> Self;
 }
@@ -1,70 +1,156 @@
-use crate::matrix::transfom_traits::{Transform2x2, Transform3x3, Transform4x4};
+use crate::matrix::decompose::compose;
+use crate::matrix::transfom_traits::{
+    Convention, DepthRange, Handedness, Transform2x2, Transform3x3, Transform4x4, VectorConvention,
+};
 use crate::matrix::{Matrix2x2, Matrix3x3, Matrix4x4};
+use crate::quaternian::Quaternion;
 use crate::vector::vector_ops::{Vector2Ops, Vector3Ops};
-use crate::vector::{Vector2, Vector3, Vector4};
+use crate::vector::{Vector2, Vector3};
 use num_traits::{Float, One, Zero};
 
+// Sign of view-space z for points in front of the camera
+fn view_z_sign<T: Float>(handedness: Handedness) -> T {
+    match handedness {
+        Handedness::Right => -T::one(),
+        Handedness::Left => T::one(),
+    }
+}
+
+// Coefficients (a, b) taking a [0, 1] near-to-far depth d01 to a * d01 + b in the
+// convention's depth range
+fn depth_remap<T: Float>(convention: Convention) -> (T, T) {
+    let one = T::one();
+    let two = one + one;
+    match (convention.depth, convention.reverse_z) {
+        (DepthRange::ZeroToOne, false) => (one, T::zero()),
+        (DepthRange::ZeroToOne, true) => (-one, one),
+        (DepthRange::NegativeOneToOne, false) => (two, -one),
+        (DepthRange::NegativeOneToOne, true) => (-two, one),
+    }
+}
+
+// Column-vector perspective projection of the near-plane rectangle. Clip w is the view
+// distance d, and depth_scale * d + depth_offset is the [0, 1] clip z before remapping.
+#[allow(clippy::too_many_arguments)]
+fn perspective_matrix<T: Float>(
+    left: T,
+    right: T,
+    bottom: T,
+    top: T,
+    near: T,
+    depth_scale: T,
+    depth_offset: T,
+    convention: Convention,
+) -> Matrix4x4<T> {
+    let two = T::one() + T::one();
+    let z_sign = view_z_sign::<T>(convention.handedness);
+    let (a, b) = depth_remap::<T>(convention);
+
+    Matrix4x4::from_2d_array([
+        [
+            two * near / (right - left),
+            T::zero(),
+            -z_sign * (right + left) / (right - left),
+            T::zero(),
+        ],
+        [
+            T::zero(),
+            two * near / (top - bottom),
+            -z_sign * (top + bottom) / (top - bottom),
+            T::zero(),
+        ],
+        [
+            T::zero(),
+            T::zero(),
+            z_sign * (a * depth_scale + b),
+            a * depth_offset,
+        ],
+        [T::zero(), T::zero(), z_sign, T::zero()],
+    ])
+}
+
+// Near-plane half extents (x, y) for a symmetric vertical field of view
+fn half_extents<T: Float>(fov_y_radians: T, aspect_ratio: T, near: T) -> (T, T) {
+    // Float:: paths so the math resolves to libm when built without std
+    let half_height = near * Float::tan(fov_y_radians / (T::one() + T::one()));
+    (half_height * aspect_ratio, half_height)
+}
+
 // Macro to implement Transform4x4 for any 4x4 matrix with Float type
 macro_rules! impl_transform_4x4 {
     ($matrix_type:ident, $float_type:ty) => {
         impl Transform4x4<$float_type> for $matrix_type<$float_type> {
-            fn perspective(
+            fn perspective_with(
                 fov_y_radians: $float_type,
                 aspect_ratio: $float_type,
                 near: $float_type,
                 far: $float_type,
+                convention: Convention,
             ) -> Self {
-                let f = <$float_type>::one()
-                    / (fov_y_radians / (<$float_type>::one() + <$float_type>::one())).tan();
+                let (x, y) = half_extents(fov_y_radians, aspect_ratio, near);
+                Self::frustum_with(-x, x, -y, y, near, far, convention)
+            }
 
-                Self::from_2d_array([
-                    [
-                        f / aspect_ratio,
-                        <$float_type>::zero(),
-                        <$float_type>::zero(),
-                        <$float_type>::zero(),
-                    ],
-                    [
-                        <$float_type>::zero(),
-                        f,
-                        <$float_type>::zero(),
-                        <$float_type>::zero(),
-                    ],
-                    [
-                        <$float_type>::zero(),
-                        <$float_type>::zero(),
-                        far / (near - far),
-                        (near * far) / (near - far),
-                    ],
-                    [
-                        <$float_type>::zero(),
-                        <$float_type>::zero(),
-                        -<$float_type>::one(),
-                        <$float_type>::zero(),
-                    ],
-                ])
+            fn perspective_infinite_with(
+                fov_y_radians: $float_type,
+                aspect_ratio: $float_type,
+                near: $float_type,
+                convention: Convention,
+            ) -> Self {
+                let (x, y) = half_extents(fov_y_radians, aspect_ratio, near);
+                // Limit of the finite depth terms as far goes to infinity
+                perspective_matrix(-x, x, -y, y, near, <$float_type>::one(), -near, convention)
+                    .into_convention(convention.vectors)
+            }
+
+            fn frustum_with(
+                left: $float_type,
+                right: $float_type,
+                bottom: $float_type,
+                top: $float_type,
+                near: $float_type,
+                far: $float_type,
+                convention: Convention,
+            ) -> Self {
+                perspective_matrix(
+                    left,
+                    right,
+                    bottom,
+                    top,
+                    near,
+                    far / (far - near),
+                    -(near * far) / (far - near),
+                    convention,
+                )
+                .into_convention(convention.vectors)
             }
 
-            fn look_at(
+            fn look_at_with(
                 eye: Vector3<$float_type>,
                 target: Vector3<$float_type>,
                 up: Vector3<$float_type>,
+                convention: Convention,
             ) -> Self {
                 let forward = (target - eye).normalize();
-                let right = forward.cross(&up).normalize();
-                let up = right.cross(&forward);
+                // Camera basis: x = right, y = up, z = backward (RH) or forward (LH)
+                let (x_axis, z_axis) = match convention.handedness {
+                    Handedness::Right => (forward.cross(&up).normalize(), -forward),
+                    Handedness::Left => (up.cross(&forward).normalize(), forward),
+                };
+                let y_axis = z_axis.cross(&x_axis);
 
                 Self::from_2d_array([
-                    [right.x(), up.x(), -forward.x(), <$float_type>::zero()],
-                    [right.y(), up.y(), -forward.y(), <$float_type>::zero()],
-                    [right.z(), up.z(), -forward.z(), <$float_type>::zero()],
+                    [x_axis.x(), x_axis.y(), x_axis.z(), -x_axis.dot(&eye)],
+                    [y_axis.x(), y_axis.y(), y_axis.z(), -y_axis.dot(&eye)],
+                    [z_axis.x(), z_axis.y(), z_axis.z(), -z_axis.dot(&eye)],
                     [
-                        -right.dot(&eye),
-                        -up.dot(&eye),
-                        forward.dot(&eye),
+                        <$float_type>::zero(),
+                        <$float_type>::zero(),
+                        <$float_type>::zero(),
                         <$float_type>::one(),
                     ],
                 ])
+                .into_convention(convention.vectors)
             }
 
             fn translation(translation: Vector3<$float_type>) -> Self {
@@ -154,9 +240,35 @@ macro_rules! impl_transform_4x4 {
                 ])
             }
 
+            fn from_trs(
+                translation: Vector3<$float_type>,
+                rotation: Quaternion<$float_type>,
+                scale: Vector3<$float_type>,
+            ) -> Self {
+                let r = rotation.to_matrix3x3();
+                let linear = Matrix3x3::from_2d_array([
+                    [
+                        r[0][0] * scale.x(),
+                        r[0][1] * scale.y(),
+                        r[0][2] * scale.z(),
+                    ],
+                    [
+                        r[1][0] * scale.x(),
+                        r[1][1] * scale.y(),
+                        r[1][2] * scale.z(),
+                    ],
+                    [
+                        r[2][0] * scale.x(),
+                        r[2][1] * scale.y(),
+                        r[2][2] * scale.z(),
+                    ],
+                ]);
+                compose(translation, linear)
+            }
+
             fn rotation_x(angle_radians: $float_type) -> Self {
-                let cos_a = angle_radians.cos();
-                let sin_a = angle_radians.sin();
+                let cos_a = Float::cos(angle_radians);
+                let sin_a = Float::sin(angle_radians);
 
                 Self::from_2d_array([
                     [
@@ -177,8 +289,8 @@ macro_rules! impl_transform_4x4 {
             }
 
             fn rotation_y(angle_radians: $float_type) -> Self {
-                let cos_a = angle_radians.cos();
-                let sin_a = angle_radians.sin();
+                let cos_a = Float::cos(angle_radians);
+                let sin_a = Float::sin(angle_radians);
 
                 Self::from_2d_array([
                     [cos_a, <$float_type>::zero(), sin_a, <$float_type>::zero()],
@@ -199,8 +311,8 @@ macro_rules! impl_transform_4x4 {
             }
 
             fn rotation_z(angle_radians: $float_type) -> Self {
-                let cos_a = angle_radians.cos();
-                let sin_a = angle_radians.sin();
+                let cos_a = Float::cos(angle_radians);
+                let sin_a = Float::sin(angle_radians);
 
                 Self::from_2d_array([
                     [cos_a, -sin_a, <$float_type>::zero(), <$float_type>::zero()],
@@ -222,8 +334,8 @@ macro_rules! impl_transform_4x4 {
 
             fn rotation_axis_angle(axis: Vector3<$float_type>, angle_radians: $float_type) -> Self {
                 let axis = axis.normalize();
-                let cos_a = angle_radians.cos();
-                let sin_a = angle_radians.sin();
+                let cos_a = Float::cos(angle_radians);
+                let sin_a = Float::sin(angle_radians);
                 let one_minus_cos = <$float_type>::one() - cos_a;
 
                 let x = axis.x();
@@ -258,15 +370,18 @@ macro_rules! impl_transform_4x4 {
                 ])
             }
 
-            fn orthographic(
+            fn orthographic_with(
                 left: $float_type,
                 right: $float_type,
                 bottom: $float_type,
                 top: $float_type,
                 near: $float_type,
                 far: $float_type,
+                convention: Convention,
             ) -> Self {
                 let two = <$float_type>::one() + <$float_type>::one();
+                let z_sign = view_z_sign::<$float_type>(convention.handedness);
+                let (a, b) = depth_remap::<$float_type>(convention);
                 Self::from_2d_array([
                     [
                         two / (right - left),
@@ -283,8 +398,8 @@ macro_rules! impl_transform_4x4 {
                     [
                         <$float_type>::zero(),
                         <$float_type>::zero(),
-                        -two / (far - near),
-                        -(far + near) / (far - near),
+                        a * z_sign / (far - near),
+                        b - a * near / (far - near),
                     ],
                     [
                         <$float_type>::zero(),
@@ -293,6 +408,14 @@ macro_rules! impl_transform_4x4 {
                         <$float_type>::one(),
                     ],
                 ])
+                .into_convention(convention.vectors)
+            }
+
+            fn into_convention(self, vectors: VectorConvention) -> Self {
+                match vectors {
+                    VectorConvention::Column => self,
+                    VectorConvention::Row => self.transpose(),
+                }
             }
         }
     };
@@ -315,8 +438,8 @@ macro_rules! impl_transform_3x3 {
             }
 
             fn rotation_2d(angle_radians: $float_type) -> Self {
-                let cos_a = angle_radians.cos();
-                let sin_a = angle_radians.sin();
+                let cos_a = Float::cos(angle_radians);
+                let sin_a = Float::sin(angle_radians);
 
                 Self::from_2d_array([
                     [cos_a, -sin_a, <$float_type>::zero()],
@@ -361,8 +484,8 @@ macro_rules! impl_transform_2x2 {
     ($matrix_type:ident, $float_type:ty) => {
         impl Transform2x2<$float_type> for $matrix_type<$float_type> {
             fn rotation_2d(angle_radians: $float_type) -> Self {
-                let cos_a = angle_radians.cos();
-                let sin_a = angle_radians.sin();
+                let cos_a = Float::cos(angle_radians);
+                let sin_a = Float::sin(angle_radians);
 
                 Self::from_2d_array([[cos_a, -sin_a], [sin_a, cos_a]])
             }
@@ -1,3 +1,530 @@
-pub struct Quaternion {}
+use core::ops::{Add, Mul, Neg, Sub};
 
-impl Quaternion {}
+use num_traits::Float;
+
+use crate::matrix::{Matrix3x3, Matrix4x4};
+use crate::vector::Vector3;
+use crate::vector::vector_ops::Vector3Ops;
+
+pub type Quaternionf32 = Quaternion<f32>;
+pub type Quaternionf64 = Quaternion<f64>;
+
+/// Rotation quaternion `w + xi + yj + zk`.
+///
+/// Rotations follow the same right-handed, column-vector convention as
+/// `Transform4x4::rotation_axis_angle`, so `q.to_matrix4x4() * v` and
+/// `q.rotate_vector(v)` agree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion<T> {
+    x: T,
+    y: T,
+    z: T,
+    w: T,
+}
+
+impl<T> Quaternion<T> {
+    #[inline]
+    pub const fn new(x: T, y: T, z: T, w: T) -> Self {
+        Quaternion { x, y, z, w }
+    }
+}
+
+impl<T: Float> Quaternion<T> {
+    /// The identity rotation
+    pub fn identity() -> Self {
+        Quaternion::new(T::zero(), T::zero(), T::zero(), T::one())
+    }
+
+    /// Build a quaternion from a vector part and a scalar part
+    pub fn from_parts(vector: Vector3<T>, scalar: T) -> Self {
+        Quaternion::new(vector.x(), vector.y(), vector.z(), scalar)
+    }
+
+    /// Create a rotation of `angle_radians` around `axis`. The axis does not need to be normalized.
+    pub fn from_axis_angle(axis: Vector3<T>, angle_radians: T) -> Self {
+        let two = T::one() + T::one();
+        let axis = axis.normalize();
+        let (sin_half, cos_half) = (angle_radians / two).sin_cos();
+        Quaternion::from_parts(axis * sin_half, cos_half)
+    }
+
+    /// Get individual components
+    pub fn x(&self) -> T {
+        self.x
+    }
+    pub fn y(&self) -> T {
+        self.y
+    }
+    pub fn z(&self) -> T {
+        self.z
+    }
+    pub fn w(&self) -> T {
+        self.w
+    }
+
+    /// The imaginary (vector) part `(x, y, z)`
+    pub fn vector_part(&self) -> Vector3<T> {
+        Vector3::new(self.x, self.y, self.z)
+    }
+
+    /// The real (scalar) part `w`
+    pub fn scalar_part(&self) -> T {
+        self.w
+    }
+
+    /// Rotation axis and angle in radians. The identity returns the X axis with a zero angle.
+    pub fn to_axis_angle(&self) -> (Vector3<T>, T) {
+        let two = T::one() + T::one();
+        let q = self.normalize();
+        let w = q.w.max(-T::one()).min(T::one());
+        let sin_half = (T::one() - w * w).sqrt();
+        if sin_half <= T::epsilon() {
+            (Vector3::new(T::one(), T::zero(), T::zero()), T::zero())
+        } else {
+            (q.vector_part() * (T::one() / sin_half), two * w.acos())
+        }
+    }
+
+    /// Calculate dot product with another quaternion
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    /// Calculate the squared length (avoids sqrt for performance)
+    pub fn length_squared(&self) -> T {
+        self.dot(self)
+    }
+
+    /// Calculate the length (magnitude) of the quaternion
+    pub fn length(&self) -> T {
+        self.length_squared().sqrt()
+    }
+
+    /// Normalize the quaternion (return the original if it has zero length)
+    pub fn normalize(&self) -> Self {
+        let len = self.length();
+        if len == T::zero() {
+            *self
+        } else {
+            *self * (T::one() / len)
+        }
+    }
+
+    /// Negate the vector part. Equal to the inverse for unit quaternions.
+    pub fn conjugate(&self) -> Self {
+        Quaternion::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Multiplicative inverse, or `None` for a zero quaternion
+    pub fn inverse(&self) -> Option<Self> {
+        let len_sq = self.length_squared();
+        if len_sq == T::zero() {
+            None
+        } else {
+            Some(self.conjugate() * (T::one() / len_sq))
+        }
+    }
+
+    /// Rotate a vector by this quaternion. The quaternion is expected to be normalized.
+    pub fn rotate_vector(&self, v: Vector3<T>) -> Vector3<T> {
+        // v' = v + 2w(u x v) + 2(u x (u x v)) with u the vector part
+        let two = T::one() + T::one();
+        let u = self.vector_part();
+        let t = u.cross(&v) * two;
+        v + t * self.w + u.cross(&t)
+    }
+
+    /// Convert to a 3x3 rotation matrix. The quaternion is expected to be normalized.
+    pub fn to_matrix3x3(&self) -> Matrix3x3<T> {
+        let one = T::one();
+        let two = one + one;
+        let (x, y, z, w) = (self.x, self.y, self.z, self.w);
+
+        Matrix3x3::from_2d_array([
+            [
+                one - two * (y * y + z * z),
+                two * (x * y - z * w),
+                two * (x * z + y * w),
+            ],
+            [
+                two * (x * y + z * w),
+                one - two * (x * x + z * z),
+                two * (y * z - x * w),
+            ],
+            [
+                two * (x * z - y * w),
+                two * (y * z + x * w),
+                one - two * (x * x + y * y),
+            ],
+        ])
+    }
+
+    /// Convert to a 4x4 homogeneous rotation matrix. The quaternion is expected to be normalized.
+    pub fn to_matrix4x4(&self) -> Matrix4x4<T> {
+        let r = self.to_matrix3x3();
+        let zero = T::zero();
+
+        Matrix4x4::from_2d_array([
+            [r[0][0], r[0][1], r[0][2], zero],
+            [r[1][0], r[1][1], r[1][2], zero],
+            [r[2][0], r[2][1], r[2][2], zero],
+            [zero, zero, zero, T::one()],
+        ])
+    }
+
+    /// Extract the rotation from a 3x3 rotation matrix (Shepperd's method)
+    pub fn from_matrix3x3(m: &Matrix3x3<T>) -> Self {
+        let one = T::one();
+        let two = one + one;
+        let quarter = one / (two * two);
+        let trace = m[0][0] + m[1][1] + m[2][2];
+
+        let q = if trace > T::zero() {
+            let s = (trace + one).sqrt() * two;
+            Quaternion::new(
+                (m[2][1] - m[1][2]) / s,
+                (m[0][2] - m[2][0]) / s,
+                (m[1][0] - m[0][1]) / s,
+                quarter * s,
+            )
+        } else if m[0][0] > m[1][1] && m[0][0] > m[2][2] {
+            let s = (one + m[0][0] - m[1][1] - m[2][2]).sqrt() * two;
+            Quaternion::new(
+                quarter * s,
+                (m[0][1] + m[1][0]) / s,
+                (m[0][2] + m[2][0]) / s,
+                (m[2][1] - m[1][2]) / s,
+            )
+        } else if m[1][1] > m[2][2] {
+            let s = (one + m[1][1] - m[0][0] - m[2][2]).sqrt() * two;
+            Quaternion::new(
+                (m[0][1] + m[1][0]) / s,
+                quarter * s,
+                (m[1][2] + m[2][1]) / s,
+                (m[0][2] - m[2][0]) / s,
+            )
+        } else {
+            let s = (one + m[2][2] - m[0][0] - m[1][1]).sqrt() * two;
+            Quaternion::new(
+                (m[0][2] + m[2][0]) / s,
+                (m[1][2] + m[2][1]) / s,
+                quarter * s,
+                (m[1][0] - m[0][1]) / s,
+            )
+        };
+
+        q.normalize()
+    }
+
+    /// Extract the rotation from the upper-left 3x3 block of a 4x4 matrix
+    pub fn from_matrix4x4(m: &Matrix4x4<T>) -> Self {
+        Quaternion::from_matrix3x3(&Matrix3x3::from_2d_array([
+            [m[0][0], m[0][1], m[0][2]],
+            [m[1][0], m[1][1], m[1][2]],
+            [m[2][0], m[2][1], m[2][2]],
+        ]))
+    }
+
+    /// Quaternion exponential
+    pub fn exp(&self) -> Self {
+        let v = self.vector_part();
+        let angle = v.length();
+        let scale = self.w.exp();
+        if angle <= T::epsilon() {
+            Quaternion::from_parts(v * scale, scale)
+        } else {
+            let (sin_a, cos_a) = angle.sin_cos();
+            Quaternion::from_parts(v * (scale * sin_a / angle), scale * cos_a)
+        }
+    }
+
+    /// Quaternion natural logarithm. For unit quaternions the scalar part is zero and the
+    /// vector part is half the rotation angle times the rotation axis.
+    pub fn ln(&self) -> Self {
+        let v = self.vector_part();
+        let v_len = v.length();
+        let len = self.length();
+        if v_len <= T::epsilon() {
+            Quaternion::from_parts(Vector3::new(T::zero(), T::zero(), T::zero()), len.ln())
+        } else {
+            Quaternion::from_parts(v * (v_len.atan2(self.w) / v_len), len.ln())
+        }
+    }
+
+    /// Normalized linear interpolation along the shortest path.
+    ///
+    /// Cheaper than `slerp` but does not move at constant angular velocity.
+    pub fn nlerp(&self, other: &Self, t: T) -> Self {
+        let end = if self.dot(other) < T::zero() {
+            -*other
+        } else {
+            *other
+        };
+        (*self * (T::one() - t) + end * t).normalize()
+    }
+
+    /// Spherical linear interpolation along the shortest path, at constant angular velocity.
+    pub fn slerp(&self, other: &Self, t: T) -> Self {
+        let end = if self.dot(other) < T::zero() {
+            -*other
+        } else {
+            *other
+        };
+        self.slerp_unchecked(&end, t)
+    }
+
+    /// Spherical interpolation through `self`, `other` using the control points `a` and `b`
+    /// (see `squad_control_point`). Gives a C1-continuous curve through a sequence of keys.
+    pub fn squad(&self, a: &Self, b: &Self, other: &Self, t: T) -> Self {
+        let two = T::one() + T::one();
+        let outer = self.slerp_unchecked(other, t);
+        let inner = a.slerp_unchecked(b, t);
+        outer.slerp_unchecked(&inner, two * t * (T::one() - t))
+    }
+
+    /// Inner control point for `current` when squad-interpolating the keys `prev, current, next`.
+    ///
+    /// The neighbours are flipped into the same hemisphere as `current`, so keys can be passed
+    /// as-is. The same sign adjustment must be applied to keys passed to `squad`.
+    pub fn squad_control_point(prev: &Self, current: &Self, next: &Self) -> Self {
+        let four = T::one() + T::one() + T::one() + T::one();
+        let align = |q: &Self| {
+            if current.dot(q) < T::zero() { -*q } else { *q }
+        };
+        let inv = current.conjugate();
+        let sum = (inv * align(next)).ln() + (inv * align(prev)).ln();
+        *current * (-sum * (T::one() / four)).exp()
+    }
+
+    // Slerp without choosing the shortest path, as required inside squad. Nearly equal inputs
+    // fall back to nlerp; nearly opposite ones turn through a perpendicular quaternion, since
+    // every great circle joins them.
+    fn slerp_unchecked(&self, end: &Self, t: T) -> Self {
+        let cos_theta = self.dot(end);
+        let tolerance = T::epsilon().sqrt();
+        if cos_theta > T::one() - tolerance {
+            return (*self * (T::one() - t) + *end * t).normalize();
+        }
+        if cos_theta < tolerance - T::one() {
+            let perpendicular = Quaternion::new(-self.y, self.x, self.w, -self.z);
+            let angle = T::from(core::f64::consts::PI).unwrap() * t;
+            return *self * angle.cos() + perpendicular * angle.sin();
+        }
+
+        let theta = cos_theta.max(-T::one()).min(T::one()).acos();
+        let sin_theta = theta.sin();
+        let a = ((T::one() - t) * theta).sin() / sin_theta;
+        let b = (t * theta).sin() / sin_theta;
+        *self * a + *end * b
+    }
+}
+
+impl<T: Float> Add for Quaternion<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Quaternion::new(
+            self.x + rhs.x,
+            self.y + rhs.y,
+            self.z + rhs.z,
+            self.w + rhs.w,
+        )
+    }
+}
+
+impl<T: Float> Sub for Quaternion<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Quaternion::new(
+            self.x - rhs.x,
+            self.y - rhs.y,
+            self.z - rhs.z,
+            self.w - rhs.w,
+        )
+    }
+}
+
+impl<T: Float> Neg for Quaternion<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Quaternion::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+impl<T: Float> Mul<T> for Quaternion<T> {
+    type Output = Self;
+    fn mul(self, scalar: T) -> Self {
+        Quaternion::new(
+            self.x * scalar,
+            self.y * scalar,
+            self.z * scalar,
+            self.w * scalar,
+        )
+    }
+}
+
+/// Hamilton product. `a * b` applies `b` first, then `a`.
+impl<T: Float> Mul for Quaternion<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Quaternion::new(
+            self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+            self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+        )
+    }
+}
+
+impl<T: Float> Mul<Vector3<T>> for Quaternion<T> {
+    type Output = Vector3<T>;
+    fn mul(self, rhs: Vector3<T>) -> Vector3<T> {
+        self.rotate_vector(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Matrix4x4f64;
+    use crate::matrix::transfom_traits::Transform4x4;
+    use crate::test_util::assert_vec_eq;
+    use crate::vector::{Vector3f64, Vector4f64};
+    use core::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_axis_angle_rotation() {
+        let q = Quaternionf64::from_axis_angle(Vector3f64::new(0.0, 0.0, 2.0), FRAC_PI_2);
+        assert_vec_eq(
+            q.rotate_vector(Vector3f64::new(1.0, 0.0, 0.0)),
+            Vector3f64::new(0.0, 1.0, 0.0),
+        );
+
+        let (axis, angle) = q.to_axis_angle();
+        assert_vec_eq(axis, Vector3f64::new(0.0, 0.0, 1.0));
+        assert!((angle - FRAC_PI_2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_hamilton_product_composes_rotations() {
+        let qx = Quaternionf64::from_axis_angle(Vector3f64::new(1.0, 0.0, 0.0), FRAC_PI_2);
+        let qz = Quaternionf64::from_axis_angle(Vector3f64::new(0.0, 0.0, 1.0), FRAC_PI_2);
+        let v = Vector3f64::new(0.0, 1.0, 0.0);
+
+        // Rotate around X first, then around Z
+        assert_vec_eq((qz * qx) * v, qz * (qx * v));
+        assert_vec_eq((qz * qx) * v, Vector3f64::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_inverse_and_conjugate() {
+        let q = Quaternionf64::new(1.0, 2.0, 3.0, 4.0);
+        let product = q * q.inverse().unwrap();
+        assert!((product.w() - 1.0).abs() < 1e-12);
+        assert!(product.vector_part().length() < 1e-12);
+
+        let unit = q.normalize();
+        assert_eq!(
+            unit.inverse()
+                .map(|i| (i - unit.conjugate()).length() < 1e-12),
+            Some(true)
+        );
+        assert_eq!(Quaternionf64::new(0.0, 0.0, 0.0, 0.0).inverse(), None);
+    }
+
+    #[test]
+    fn test_matrix_round_trip() {
+        let axis = Vector3f64::new(1.0, -2.0, 0.5);
+        let angle = 2.5;
+        let q = Quaternionf64::from_axis_angle(axis, angle);
+
+        let expected = Matrix4x4f64::rotation_axis_angle(axis, angle);
+        let actual = q.to_matrix4x4();
+        for (a, b) in actual.as_slice().iter().zip(expected.as_slice()) {
+            assert!((a - b).abs() < 1e-10);
+        }
+
+        let back = Quaternionf64::from_matrix4x4(&actual);
+        // q and -q represent the same rotation
+        assert!(back.dot(&q).abs() > 1.0 - 1e-10);
+
+        let v = Vector3f64::new(0.3, 0.7, -1.1);
+        let rotated = actual * Vector4f64::new(v.x(), v.y(), v.z(), 1.0);
+        assert_vec_eq(q * v, Vector3f64::new(rotated[0], rotated[1], rotated[2]));
+    }
+
+    #[test]
+    fn test_from_matrix_half_turns() {
+        // Exercise the non-positive trace branches
+        for axis in [
+            Vector3f64::new(1.0, 0.0, 0.0),
+            Vector3f64::new(0.0, 1.0, 0.0),
+            Vector3f64::new(0.0, 0.0, 1.0),
+        ] {
+            let q = Quaternionf64::from_axis_angle(axis, core::f64::consts::PI);
+            let back = Quaternionf64::from_matrix3x3(&q.to_matrix3x3());
+            assert!(back.dot(&q).abs() > 1.0 - 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_slerp_and_nlerp() {
+        let z = Vector3f64::new(0.0, 0.0, 1.0);
+        let a = Quaternionf64::identity();
+        let b = Quaternionf64::from_axis_angle(z, FRAC_PI_2);
+
+        let mid = a.slerp(&b, 0.5);
+        let (_, angle) = mid.to_axis_angle();
+        assert!((angle - FRAC_PI_2 / 2.0).abs() < 1e-10);
+        assert!((mid.length() - 1.0).abs() < 1e-12);
+
+        // -b is the same rotation; slerp must still take the short way round
+        let mid_flipped = a.slerp(&-b, 0.5);
+        assert!(mid_flipped.dot(&mid).abs() > 1.0 - 1e-10);
+
+        let n = a.nlerp(&-b, 0.5);
+        assert!((n.length() - 1.0).abs() < 1e-12);
+        assert!(n.dot(&mid).abs() > 1.0 - 1e-10);
+
+        assert!(a.slerp(&b, 0.0).dot(&a) > 1.0 - 1e-12);
+        assert!(a.slerp(&b, 1.0).dot(&b) > 1.0 - 1e-12);
+    }
+
+    #[test]
+    fn test_exp_ln_round_trip() {
+        let q = Quaternionf64::from_axis_angle(Vector3f64::new(1.0, 2.0, 3.0), 1.2);
+        let back = q.ln().exp();
+        assert!((back - q).length() < 1e-12);
+        assert!(q.ln().w().abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_squad_passes_through_keys() {
+        let y = Vector3f64::new(0.0, 1.0, 0.0);
+        let keys = [
+            Quaternionf64::identity(),
+            Quaternionf64::from_axis_angle(y, 0.5),
+            Quaternionf64::from_axis_angle(Vector3f64::new(1.0, 1.0, 0.0), 1.3),
+            Quaternionf64::from_axis_angle(y, 2.0),
+        ];
+        let s1 = Quaternionf64::squad_control_point(&keys[0], &keys[1], &keys[2]);
+        let s2 = Quaternionf64::squad_control_point(&keys[1], &keys[2], &keys[3]);
+
+        let start = keys[1].squad(&s1, &s2, &keys[2], 0.0);
+        let end = keys[1].squad(&s1, &s2, &keys[2], 1.0);
+        assert!(start.dot(&keys[1]).abs() > 1.0 - 1e-10);
+        assert!(end.dot(&keys[2]).abs() > 1.0 - 1e-10);
+
+        let mid = keys[1].squad(&s1, &s2, &keys[2], 0.5);
+        assert!((mid.length() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_squad_between_opposite_signs() {
+        let q = Quaternionf64::from_axis_angle(Vector3f64::new(0.0, 0.0, 1.0), 0.7);
+        for t in [0.0, 0.25, 0.5, 1.0] {
+            let r = q.squad(&q, &-q, &-q, t);
+            assert!((r.length() - 1.0).abs() < 1e-12, "t = {t}: {r:?}");
+        }
+        assert!(q.squad(&q, &-q, &-q, 1.0).dot(&-q) > 1.0 - 1e-12);
+    }
+}
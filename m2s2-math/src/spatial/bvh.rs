@@ -0,0 +1,427 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::array;
+
+use num_traits::Float;
+
+use crate::geometry::{Aabb3, Containment, Frustum, Ray3};
+use crate::spatial::ray_entry;
+use crate::vector::Vector3;
+
+const BIN_COUNT: usize = 12;
+const MAX_LEAF_SIZE: usize = 4;
+
+/// Bounding volume hierarchy over primitives given by their bounding boxes.
+///
+/// Queries report primitives by their index in the slice of bounds the tree was built from
+/// and leave exact tests against the primitives themselves to the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bvh<T> {
+    nodes: Vec<Node<T>>,
+    // Primitive indices in leaf order
+    indices: Vec<usize>,
+    bounds: Vec<Aabb3<T>>,
+}
+
+// Leaves hold `count` primitives starting at `indices[first]`. Inner nodes have a count of
+// zero and their children at `first` and `first + 1`, always after the parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Node<T> {
+    bounds: Aabb3<T>,
+    first: usize,
+    count: usize,
+}
+
+impl<T: Float> Bvh<T> {
+    /// Build with the surface area heuristic from the bounds of each primitive
+    pub fn build(bounds: &[Aabb3<T>]) -> Self {
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            indices: (0..bounds.len()).collect(),
+            bounds: bounds.to_vec(),
+        };
+        if !bounds.is_empty() {
+            let centers: Vec<Vector3<T>> = bounds.iter().map(Aabb3::center).collect();
+            bvh.nodes.push(Node {
+                bounds: Aabb3::empty(),
+                first: 0,
+                count: 0,
+            });
+            bvh.split(0, 0, bounds.len(), &centers);
+        }
+        bvh
+    }
+
+    fn leaf_bounds(&self, first: usize, count: usize) -> Aabb3<T> {
+        self.indices[first..first + count]
+            .iter()
+            .fold(Aabb3::empty(), |sum, &i| sum.union(&self.bounds[i]))
+    }
+
+    // Make node `n` over `indices[first..first + count]`, splitting it where the surface
+    // area heuristic says it pays off
+    fn split(&mut self, n: usize, first: usize, count: usize, centers: &[Vector3<T>]) {
+        let bounds = self.leaf_bounds(first, count);
+        self.nodes[n] = Node {
+            bounds,
+            first,
+            count,
+        };
+        if count == 1 {
+            return;
+        }
+
+        let center_bounds = self.indices[first..first + count]
+            .iter()
+            .fold(Aabb3::empty(), |sum, &i| sum.expand(centers[i]));
+        let bins = T::from(BIN_COUNT).unwrap();
+        let bin_of = |axis: usize, center: &Vector3<T>| {
+            let (low, high) = (center_bounds.min[axis], center_bounds.max[axis]);
+            let bin = ((center[axis] - low) * bins / (high - low)).to_usize();
+            bin.unwrap_or(0).min(BIN_COUNT - 1)
+        };
+
+        // Costs are scaled by the parent's area: traversing costs one, each primitive one
+        let mut best: Option<(T, usize, usize)> = None;
+        for axis in 0..3 {
+            if center_bounds.max[axis] <= center_bounds.min[axis] {
+                continue;
+            }
+            let mut binned = [(Aabb3::empty(), 0usize); BIN_COUNT];
+            for &i in &self.indices[first..first + count] {
+                let bin = &mut binned[bin_of(axis, &centers[i])];
+                *bin = (bin.0.union(&self.bounds[i]), bin.1 + 1);
+            }
+
+            let mut right = [(T::zero(), 0usize); BIN_COUNT];
+            let mut sum = (Aabb3::empty(), 0);
+            for split in (1..BIN_COUNT).rev() {
+                sum = (sum.0.union(&binned[split].0), sum.1 + binned[split].1);
+                right[split] = (sum.0.surface_area(), sum.1);
+            }
+            let mut left = (Aabb3::empty(), 0);
+            for split in 1..BIN_COUNT {
+                left = (
+                    left.0.union(&binned[split - 1].0),
+                    left.1 + binned[split - 1].1,
+                );
+                let (right_area, right_count) = right[split];
+                if left.1 == 0 || right_count == 0 {
+                    continue;
+                }
+                let cost = bounds.surface_area()
+                    + left.0.surface_area() * T::from(left.1).unwrap()
+                    + right_area * T::from(right_count).unwrap();
+                if best.is_none_or(|(lowest, _, _)| cost < lowest) {
+                    best = Some((cost, axis, split));
+                }
+            }
+        }
+
+        // Coincident centers cannot be separated
+        let Some((cost, axis, split)) = best else {
+            return;
+        };
+        let leaf_cost = bounds.surface_area() * T::from(count).unwrap();
+        if count <= MAX_LEAF_SIZE && cost >= leaf_cost {
+            return;
+        }
+
+        let mut middle = first;
+        for i in first..first + count {
+            if bin_of(axis, &centers[self.indices[i]]) < split {
+                self.indices.swap(i, middle);
+                middle += 1;
+            }
+        }
+        let child = self.nodes.len();
+        let placeholder = self.nodes[n];
+        self.nodes.extend([placeholder; 2]);
+        self.nodes[n] = Node {
+            bounds,
+            first: child,
+            count: 0,
+        };
+        self.split(child, first, middle - first, centers);
+        self.split(child + 1, middle, first + count - middle, centers);
+    }
+
+    /// Recompute the boxes after primitives move, keeping the tree shape. Queries slow down
+    /// as primitives drift from where they were at build time, so rebuild now and then.
+    pub fn refit(&mut self, bounds: &[Aabb3<T>]) {
+        assert_eq!(
+            bounds.len(),
+            self.bounds.len(),
+            "refit needs the bounds of the primitives the tree was built from"
+        );
+        self.bounds.copy_from_slice(bounds);
+        for n in (0..self.nodes.len()).rev() {
+            let node = self.nodes[n];
+            self.nodes[n].bounds = if node.count > 0 {
+                self.leaf_bounds(node.first, node.count)
+            } else {
+                let (left, right) = (&self.nodes[node.first], &self.nodes[node.first + 1]);
+                left.bounds.union(&right.bounds)
+            };
+        }
+    }
+
+    /// Number of primitives
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Box around every primitive, `None` when the tree is empty
+    pub fn bounds(&self) -> Option<Aabb3<T>> {
+        self.nodes.first().map(|root| root.bounds)
+    }
+
+    // Visit every primitive whose box `classify` does not put outside, skipping the tests
+    // below nodes found entirely inside
+    fn query(
+        &self,
+        mut classify: impl FnMut(&Aabb3<T>) -> Containment,
+        mut visit: impl FnMut(usize),
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let mut stack = vec![(0, false)];
+        while let Some((n, inside)) = stack.pop() {
+            let node = &self.nodes[n];
+            let inside = inside
+                || match classify(&node.bounds) {
+                    Containment::Outside => continue,
+                    Containment::Intersecting => false,
+                    Containment::Inside => true,
+                };
+            if node.count == 0 {
+                stack.extend([(node.first + 1, inside), (node.first, inside)]);
+                continue;
+            }
+            for &i in &self.indices[node.first..node.first + node.count] {
+                if inside || classify(&self.bounds[i]) != Containment::Outside {
+                    visit(i);
+                }
+            }
+        }
+    }
+
+    /// Calls `visit` with every primitive whose box overlaps `region`
+    pub fn query_aabb(&self, region: &Aabb3<T>, visit: impl FnMut(usize)) {
+        let classify = |bounds: &Aabb3<T>| {
+            if !region.intersects(bounds) {
+                Containment::Outside
+            } else if region.contains(bounds) {
+                Containment::Inside
+            } else {
+                Containment::Intersecting
+            }
+        };
+        self.query(classify, visit);
+    }
+
+    /// Calls `visit` with every primitive whose box is at least partly inside the frustum.
+    /// Like `Frustum::intersects_aabb` this is conservative near the frustum's corners.
+    pub fn query_frustum(&self, frustum: &Frustum<T>, visit: impl FnMut(usize)) {
+        self.query(|bounds| frustum.classify_aabb(bounds), visit);
+    }
+
+    /// Closest hit along `ray` within `max_distance`, as the primitive index and distance.
+    ///
+    /// `hit` runs the exact test against a primitive whose box the ray crosses and returns
+    /// the distance along the ray, in multiples of its direction like `RayHit`. Nodes beyond
+    /// the closest hit so far are skipped and nearer children are searched first.
+    pub fn cast_ray(
+        &self,
+        ray: &Ray3<T>,
+        max_distance: T,
+        mut hit: impl FnMut(usize) -> Option<T>,
+    ) -> Option<(usize, T)> {
+        let root = self.nodes.first()?;
+        let inverse = Vector3::from_array(array::from_fn(|i| T::one() / ray.direction[i]));
+        let mut closest: Option<(usize, T)> = None;
+        let mut limit = max_distance;
+        let mut stack = vec![(0, ray_entry(ray, &inverse, &root.bounds, limit)?)];
+
+        while let Some((n, entry)) = stack.pop() {
+            if entry > limit {
+                continue;
+            }
+            let node = &self.nodes[n];
+            if node.count == 0 {
+                let near = ray_entry(ray, &inverse, &self.nodes[node.first].bounds, limit);
+                let far = ray_entry(ray, &inverse, &self.nodes[node.first + 1].bounds, limit);
+                let (near, far) = match (near, far) {
+                    (Some(a), Some(b)) if b < a => ((node.first + 1, b), Some((node.first, a))),
+                    (Some(a), b) => ((node.first, a), b.map(|b| (node.first + 1, b))),
+                    (None, Some(b)) => ((node.first + 1, b), None),
+                    (None, None) => continue,
+                };
+                stack.extend(far);
+                stack.push(near);
+                continue;
+            }
+
+            for &i in &self.indices[node.first..node.first + node.count] {
+                if ray_entry(ray, &inverse, &self.bounds[i], limit).is_none() {
+                    continue;
+                }
+                let Some(distance) = hit(i) else {
+                    continue;
+                };
+                let closer = match closest {
+                    Some(_) => distance < limit,
+                    None => distance <= limit,
+                };
+                if distance >= T::zero() && closer {
+                    limit = distance;
+                    closest = Some((i, distance));
+                }
+            }
+        }
+        closest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::Bvh;
+    use crate::geometry::{Aabb3f64, Frustum, Plane, Ray3f64, Triangle};
+    use crate::spatial::test_util::sorted;
+    use crate::test_util::v3;
+
+    // Bumpy 20 x 20 grid of quads split into triangles, in x and y from 0 to 20
+    fn terrain() -> Vec<Triangle<f64>> {
+        let height = |x: f64, y: f64| (x * 0.7).sin() + (y * 0.4).cos();
+        let point = |x: f64, y: f64| v3(x, y, height(x, y));
+        let mut triangles = Vec::new();
+        for i in 0..20 {
+            for j in 0..20 {
+                let (x, y) = (i as f64, j as f64);
+                let corners = [
+                    point(x, y),
+                    point(x + 1.0, y),
+                    point(x + 1.0, y + 1.0),
+                    point(x, y + 1.0),
+                ];
+                triangles.push(Triangle::new(corners[0], corners[1], corners[2]));
+                triangles.push(Triangle::new(corners[0], corners[2], corners[3]));
+            }
+        }
+        triangles
+    }
+
+    fn bounds_of(triangles: &[Triangle<f64>]) -> Vec<Aabb3f64> {
+        triangles.iter().map(Triangle::bounds).collect()
+    }
+
+    fn brute_force_ray(triangles: &[Triangle<f64>], ray: &Ray3f64) -> Option<(usize, f64)> {
+        triangles
+            .iter()
+            .enumerate()
+            .filter_map(|(i, t)| ray.intersect_triangle(t).map(|hit| (i, hit.hit.distance)))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+    }
+
+    #[test]
+    fn test_bvh_cast_ray_matches_brute_force() {
+        let triangles = terrain();
+        let bvh = Bvh::build(&bounds_of(&triangles));
+        assert_eq!(bvh.len(), 800);
+        let root = bvh.bounds().unwrap();
+        assert!(triangles.iter().all(|t| root.contains(&t.bounds())));
+
+        for k in 0..50 {
+            let k = k as f64;
+            let origin = v3((k * 7.3) % 20.0, (k * 3.1) % 20.0, 5.0);
+            let direction = v3((k * 0.37).sin(), (k * 0.53).cos(), -1.5);
+            let ray = Ray3f64::new(origin, direction);
+            let hit = bvh.cast_ray(&ray, f64::INFINITY, |i| {
+                ray.intersect_triangle(&triangles[i])
+                    .map(|hit| hit.hit.distance)
+            });
+            let expected = brute_force_ray(&triangles, &ray);
+            assert_eq!(hit.map(|h| h.1), expected.map(|h| h.1));
+        }
+
+        // Limited reach and rays pointing away
+        let down = Ray3f64::new(v3(5.5, 5.5, 10.0), v3(0.0, 0.0, -1.0));
+        let hit = |i: usize| {
+            down.intersect_triangle(&triangles[i])
+                .map(|h| h.hit.distance)
+        };
+        assert!(bvh.cast_ray(&down, 20.0, hit).is_some());
+        assert!(bvh.cast_ray(&down, 5.0, hit).is_none());
+        let up = Ray3f64::new(v3(5.5, 5.5, 10.0), v3(0.0, 0.0, 1.0));
+        assert!(bvh.cast_ray(&up, f64::INFINITY, |_| Some(1.0)).is_none());
+    }
+
+    #[test]
+    fn test_bvh_region_queries() {
+        let triangles = terrain();
+        let bounds = bounds_of(&triangles);
+        let bvh = Bvh::build(&bounds);
+
+        let region = Aabb3f64::new(v3(3.5, 7.2, -5.0), v3(9.1, 8.4, 0.5));
+        let mut found = Vec::new();
+        bvh.query_aabb(&region, |i| found.push(i));
+        let expected = (0..bounds.len()).filter(|&i| region.intersects(&bounds[i]));
+        assert_eq!(sorted(found), expected.collect::<Vec<_>>());
+
+        // Box-shaped frustum from x in [2, 6] and y in [10, 13]
+        let frustum = Frustum::new([
+            Plane::new(v3(1.0, 0.0, 0.0), -2.0),
+            Plane::new(v3(-1.0, 0.0, 0.0), 6.0),
+            Plane::new(v3(0.0, 1.0, 0.0), -10.0),
+            Plane::new(v3(0.0, -1.0, 0.0), 13.0),
+            Plane::new(v3(0.0, 0.0, 1.0), 10.0),
+            Plane::new(v3(0.0, 0.0, -1.0), 10.0),
+        ]);
+        let mut found = Vec::new();
+        bvh.query_frustum(&frustum, |i| found.push(i));
+        let expected = (0..bounds.len()).filter(|&i| frustum.intersects_aabb(&bounds[i]));
+        assert_eq!(sorted(found), expected.collect::<Vec<_>>());
+
+        let empty = Bvh::<f64>::build(&[]);
+        assert!(empty.is_empty() && empty.bounds().is_none());
+        empty.query_aabb(&region, |_| panic!("empty tree has nothing to visit"));
+    }
+
+    #[test]
+    fn test_bvh_refit() {
+        let mut triangles = terrain();
+        let mut bvh = Bvh::build(&bounds_of(&triangles));
+
+        // Lift half the terrain out of the way of a ray that used to hit it
+        let lift = v3(0.0, 0.0, 50.0);
+        for triangle in triangles.iter_mut().take(400) {
+            *triangle = Triangle::new(triangle.a + lift, triangle.b + lift, triangle.c + lift);
+        }
+        bvh.refit(&bounds_of(&triangles));
+        let root = bvh.bounds().unwrap();
+        assert!(triangles.iter().all(|t| root.contains(&t.bounds())));
+
+        let ray = Ray3f64::new(v3(4.5, 4.5, 10.0), v3(0.0, 0.0, -1.0));
+        let hit = bvh.cast_ray(&ray, f64::INFINITY, |i| {
+            ray.intersect_triangle(&triangles[i])
+                .map(|hit| hit.hit.distance)
+        });
+        assert_eq!(hit, brute_force_ray(&triangles, &ray));
+        assert!(hit.is_none());
+
+        let ray = Ray3f64::new(v3(14.5, 4.5, 10.0), v3(0.0, 0.0, -1.0));
+        let hit = bvh.cast_ray(&ray, f64::INFINITY, |i| {
+            ray.intersect_triangle(&triangles[i])
+                .map(|hit| hit.hit.distance)
+        });
+        assert_eq!(hit, brute_force_ray(&triangles, &ray));
+        assert!(hit.is_some());
+    }
+}
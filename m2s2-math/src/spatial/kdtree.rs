@@ -0,0 +1,291 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use num_traits::Float;
+
+use crate::geometry::dot;
+use crate::vector::Vector;
+
+/// Point found by a `KdTree` query, by its index in the slice the tree was built from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Neighbor<T> {
+    pub index: usize,
+    pub distance_squared: T,
+}
+
+/// Static kd-tree over points for nearest-neighbor and radius queries.
+///
+/// The tree is stored implicitly: each range of points is split at its median along the axis
+/// where the points spread the most, with the median point between the two halves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KdTree<T, const D: usize> {
+    points: Vec<Vector<T, D>>,
+    indices: Vec<usize>,
+    // Split axis of the range each point is the median of
+    axes: Vec<usize>,
+}
+
+pub type KdTree2<T> = KdTree<T, 2>;
+pub type KdTree3<T> = KdTree<T, 3>;
+
+// Gathers neighbors during a search, which skips everything further than `limit`
+trait Collector<T> {
+    fn limit(&self) -> T;
+    fn offer(&mut self, neighbor: Neighbor<T>);
+}
+
+// The `k` nearest so far, sorted by distance
+struct Nearest<T> {
+    k: usize,
+    found: Vec<Neighbor<T>>,
+}
+
+impl<T: Float> Collector<T> for Nearest<T> {
+    fn limit(&self) -> T {
+        if self.found.len() < self.k {
+            T::infinity()
+        } else {
+            self.found[self.k - 1].distance_squared
+        }
+    }
+
+    fn offer(&mut self, neighbor: Neighbor<T>) {
+        let position = self
+            .found
+            .partition_point(|n| n.distance_squared <= neighbor.distance_squared);
+        self.found.insert(position, neighbor);
+        self.found.truncate(self.k);
+    }
+}
+
+struct WithinRadius<T> {
+    radius_squared: T,
+    found: Vec<Neighbor<T>>,
+}
+
+impl<T: Float> Collector<T> for WithinRadius<T> {
+    fn limit(&self) -> T {
+        self.radius_squared
+    }
+
+    fn offer(&mut self, neighbor: Neighbor<T>) {
+        self.found.push(neighbor);
+    }
+}
+
+fn compare<T: Float>(a: T, b: T) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+impl<T: Float, const D: usize> KdTree<T, D> {
+    pub fn build(points: &[Vector<T, D>]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        let mut axes = alloc::vec![0; points.len()];
+        Self::split(points, &mut order, &mut axes);
+        KdTree {
+            points: order.iter().map(|&i| points[i]).collect(),
+            indices: order,
+            axes,
+        }
+    }
+
+    fn split(points: &[Vector<T, D>], order: &mut [usize], axes: &mut [usize]) {
+        if order.is_empty() {
+            return;
+        }
+        let spread = |axis: usize| {
+            let (low, high) = order
+                .iter()
+                .fold((T::infinity(), T::neg_infinity()), |(low, high), &i| {
+                    (low.min(points[i][axis]), high.max(points[i][axis]))
+                });
+            high - low
+        };
+        let axis = (0..D)
+            .max_by(|&a, &b| compare(spread(a), spread(b)))
+            .unwrap_or(0);
+
+        let middle = order.len() / 2;
+        order.select_nth_unstable_by(middle, |&a, &b| compare(points[a][axis], points[b][axis]));
+        axes[middle] = axis;
+        let (left, right) = order.split_at_mut(middle);
+        let (left_axes, right_axes) = axes.split_at_mut(middle);
+        Self::split(points, left, left_axes);
+        Self::split(points, &mut right[1..], &mut right_axes[1..]);
+    }
+
+    /// Number of points
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    // Offer the points of `points[low..high]` near enough to `query`, searching the half
+    // containing it first so the limit shrinks early
+    fn search(
+        &self,
+        low: usize,
+        high: usize,
+        query: &Vector<T, D>,
+        collector: &mut impl Collector<T>,
+    ) {
+        if low >= high {
+            return;
+        }
+        let middle = low + (high - low) / 2;
+        let point = &self.points[middle];
+        let offset = *query - *point;
+        let distance_squared = dot(&offset, &offset);
+        if distance_squared <= collector.limit() {
+            collector.offer(Neighbor {
+                index: self.indices[middle],
+                distance_squared,
+            });
+        }
+
+        let axis = self.axes[middle];
+        let across = query[axis] - point[axis];
+        let (near, far) = if across < T::zero() {
+            ((low, middle), (middle + 1, high))
+        } else {
+            ((middle + 1, high), (low, middle))
+        };
+        self.search(near.0, near.1, query, collector);
+        if across * across <= collector.limit() {
+            self.search(far.0, far.1, query, collector);
+        }
+    }
+
+    /// Closest point to `query`, `None` when the tree is empty
+    pub fn nearest(&self, query: Vector<T, D>) -> Option<Neighbor<T>> {
+        self.k_nearest(query, 1).first().copied()
+    }
+
+    /// Up to `k` points closest to `query`, nearest first
+    pub fn k_nearest(&self, query: Vector<T, D>, k: usize) -> Vec<Neighbor<T>> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut nearest = Nearest {
+            k,
+            found: Vec::with_capacity(k.min(self.len()) + 1),
+        };
+        self.search(0, self.len(), &query, &mut nearest);
+        nearest.found
+    }
+
+    /// Every point within `radius` of `query`, including on the boundary, in no particular
+    /// order
+    pub fn within_radius(&self, query: Vector<T, D>, radius: T) -> Vec<Neighbor<T>> {
+        let mut within = WithinRadius {
+            radius_squared: radius * radius,
+            found: Vec::new(),
+        };
+        self.search(0, self.len(), &query, &mut within);
+        within.found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{KdTree, KdTree2, KdTree3, Neighbor};
+    use crate::geometry::dot;
+    use crate::spatial::test_util::{scattered_points, sorted};
+    use crate::vector::{Vector, Vector2f64, Vector3f64};
+
+    fn brute_force<const D: usize>(
+        points: &[Vector<f64, D>],
+        query: Vector<f64, D>,
+    ) -> Vec<Neighbor<f64>> {
+        let mut all: Vec<_> = points
+            .iter()
+            .enumerate()
+            .map(|(index, &p)| {
+                let offset = p - query;
+                Neighbor {
+                    index,
+                    distance_squared: dot(&offset, &offset),
+                }
+            })
+            .collect();
+        all.sort_unstable_by(|a, b| a.distance_squared.partial_cmp(&b.distance_squared).unwrap());
+        all
+    }
+
+    fn distances(neighbors: &[Neighbor<f64>]) -> Vec<f64> {
+        neighbors.iter().map(|n| n.distance_squared).collect()
+    }
+
+    #[test]
+    fn test_kdtree_nearest_matches_brute_force() {
+        let points = scattered_points::<3>(500, 0.0, 100.0, 7);
+        let tree = KdTree3::build(&points);
+        assert_eq!(tree.len(), 500);
+        for query in scattered_points::<3>(40, 0.0, 100.0, 99) {
+            let expected = brute_force(&points, query);
+            let nearest = tree.nearest(query).unwrap();
+            assert_eq!(nearest.distance_squared, expected[0].distance_squared);
+            assert_eq!(points[nearest.index], points[expected[0].index]);
+
+            let k_nearest = tree.k_nearest(query, 10);
+            assert_eq!(distances(&k_nearest), distances(&expected[..10]));
+        }
+
+        // The point itself is its own nearest neighbor
+        assert_eq!(tree.nearest(points[123]).unwrap().index, 123);
+    }
+
+    #[test]
+    fn test_kdtree_within_radius() {
+        let points = scattered_points::<2>(400, 0.0, 100.0, 3);
+        let tree = KdTree2::build(&points);
+        for query in scattered_points::<2>(20, 0.0, 100.0, 11) {
+            let found = tree
+                .within_radius(query, 12.5)
+                .iter()
+                .map(|n| n.index)
+                .collect();
+            let expected = brute_force(&points, query)
+                .iter()
+                .filter(|n| n.distance_squared <= 12.5 * 12.5)
+                .map(|n| n.index)
+                .collect();
+            assert_eq!(sorted(found), sorted(expected));
+        }
+
+        // Boundary points count
+        let grid = [
+            Vector2f64::new(0.0, 0.0),
+            Vector2f64::new(1.0, 0.0),
+            Vector2f64::new(0.0, 1.0),
+            Vector2f64::new(2.0, 2.0),
+        ];
+        let tree = KdTree2::build(&grid);
+        assert_eq!(tree.within_radius(Vector2f64::new(0.0, 0.0), 1.0).len(), 3);
+    }
+
+    #[test]
+    fn test_kdtree_edge_cases() {
+        let empty = KdTree3::<f64>::build(&[]);
+        assert!(empty.is_empty());
+        assert!(empty.nearest(Vector3f64::new(0.0, 0.0, 0.0)).is_none());
+        assert!(
+            empty
+                .k_nearest(Vector3f64::new(0.0, 0.0, 0.0), 3)
+                .is_empty()
+        );
+
+        // Duplicates and more neighbors asked for than there are points
+        let same = [Vector3f64::new(1.0, 2.0, 3.0); 5];
+        let tree = KdTree::build(&same);
+        let all = tree.k_nearest(Vector3f64::new(0.0, 0.0, 0.0), 8);
+        assert_eq!(all.len(), 5);
+        assert!(all.iter().all(|n| n.distance_squared == 14.0));
+        assert!(tree.k_nearest(Vector3f64::new(0.0, 0.0, 0.0), 0).is_empty());
+    }
+}
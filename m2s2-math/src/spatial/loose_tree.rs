@@ -0,0 +1,329 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::array;
+
+use num_traits::Float;
+
+use crate::geometry::{Aabb, Ray};
+use crate::spatial::ray_entry;
+use crate::vector::Vector;
+
+/// Loose quadtree (`D = 2`) or octree (`D = 3`) of values keyed by bounding boxes.
+///
+/// Each node covers a cell of the world bounds but holds entries reaching up to half a cell
+/// past it on every side, so an entry lives in the deepest node whose cell is at least its
+/// size and contains its center. Entries that do not fit, including those outside the world
+/// bounds, stay in the root. Ids returned by `insert` are reused after `remove`.
+#[derive(Debug, Clone)]
+pub struct LooseTree<T, V, const D: usize> {
+    max_depth: usize,
+    nodes: Vec<Node<T, D>>,
+    entries: Vec<Option<Entry<T, V, D>>>,
+    free: Vec<usize>,
+}
+
+pub type Quadtree<T, V> = LooseTree<T, V, 2>;
+pub type Octree<T, V> = LooseTree<T, V, 3>;
+
+// The `1 << D` children of a node are created together, starting at `children`
+#[derive(Debug, Clone)]
+struct Node<T, const D: usize> {
+    cell: Aabb<T, D>,
+    children: Option<usize>,
+    ids: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry<T, V, const D: usize> {
+    bounds: Aabb<T, D>,
+    value: V,
+    node: usize,
+}
+
+// Cell grown by half its size on every side
+fn loose<T: Float, const D: usize>(cell: &Aabb<T, D>) -> Aabb<T, D> {
+    let half = cell.extents();
+    Aabb::new(cell.min - half, cell.max + half)
+}
+
+impl<T: Float, V, const D: usize> LooseTree<T, V, D> {
+    /// Empty tree over `bounds`, subdivided at most `max_depth` times
+    pub fn new(bounds: Aabb<T, D>, max_depth: usize) -> Self {
+        LooseTree {
+            max_depth,
+            nodes: vec![Node {
+                cell: bounds,
+                children: None,
+                ids: Vec::new(),
+            }],
+            entries: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Bounds of the root cell
+    pub fn bounds(&self) -> Aabb<T, D> {
+        self.nodes[0].cell
+    }
+
+    /// Number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.free.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, id: usize) -> Option<&V> {
+        self.entry(id).map(|entry| &entry.value)
+    }
+
+    pub fn get_mut(&mut self, id: usize) -> Option<&mut V> {
+        self.entries
+            .get_mut(id)
+            .and_then(Option::as_mut)
+            .map(|entry| &mut entry.value)
+    }
+
+    /// Bounds an entry was inserted or last moved with
+    pub fn entry_bounds(&self, id: usize) -> Option<Aabb<T, D>> {
+        self.entry(id).map(|entry| entry.bounds)
+    }
+
+    fn entry(&self, id: usize) -> Option<&Entry<T, V, D>> {
+        self.entries.get(id).and_then(Option::as_ref)
+    }
+
+    // Node for an entry with these bounds, creating nodes on the way down as needed
+    fn node_for(&mut self, bounds: &Aabb<T, D>) -> usize {
+        let (size, center) = (bounds.size(), bounds.center());
+        let mut n = 0;
+        if !self.nodes[0].cell.contains_point(center) {
+            return n;
+        }
+        for _ in 0..self.max_depth {
+            let cell = self.nodes[n].cell;
+            let half = cell.extents();
+            if (0..D).any(|i| size[i] > half[i]) {
+                break;
+            }
+            let first = match self.nodes[n].children {
+                Some(first) => first,
+                None => self.subdivide(n),
+            };
+            let middle = cell.center();
+            let child = (0..D)
+                .filter(|&i| center[i] >= middle[i])
+                .fold(0, |child, i| child | (1 << i));
+            n = first + child;
+        }
+        n
+    }
+
+    fn subdivide(&mut self, n: usize) -> usize {
+        let cell = self.nodes[n].cell;
+        let middle = cell.center();
+        let first = self.nodes.len();
+        for child in 0..1usize << D {
+            let upper = |i: usize| child & (1 << i) != 0;
+            self.nodes.push(Node {
+                cell: Aabb::new(
+                    Vector::from_array(array::from_fn(|i| {
+                        if upper(i) { middle[i] } else { cell.min[i] }
+                    })),
+                    Vector::from_array(array::from_fn(|i| {
+                        if upper(i) { cell.max[i] } else { middle[i] }
+                    })),
+                ),
+                children: None,
+                ids: Vec::new(),
+            });
+        }
+        self.nodes[n].children = Some(first);
+        first
+    }
+
+    /// Add a value and return its id
+    pub fn insert(&mut self, bounds: Aabb<T, D>, value: V) -> usize {
+        let node = self.node_for(&bounds);
+        let entry = Some(Entry {
+            bounds,
+            value,
+            node,
+        });
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.entries[id] = entry;
+                id
+            }
+            None => {
+                self.entries.push(entry);
+                self.entries.len() - 1
+            }
+        };
+        self.nodes[node].ids.push(id);
+        id
+    }
+
+    fn unlink(&mut self, id: usize, node: usize) {
+        let ids = &mut self.nodes[node].ids;
+        if let Some(position) = ids.iter().position(|&other| other == id) {
+            ids.swap_remove(position);
+        }
+    }
+
+    /// Take out an entry, `None` when the id is not in the tree
+    pub fn remove(&mut self, id: usize) -> Option<V> {
+        let entry = self.entries.get_mut(id)?.take()?;
+        self.unlink(id, entry.node);
+        self.free.push(id);
+        Some(entry.value)
+    }
+
+    /// Give an entry new bounds, moving it to another node when needed. False when the id is
+    /// not in the tree.
+    pub fn move_to(&mut self, id: usize, bounds: Aabb<T, D>) -> bool {
+        let Some(old) = self.entry(id).map(|entry| entry.node) else {
+            return false;
+        };
+        let node = self.node_for(&bounds);
+        if node != old {
+            self.unlink(id, old);
+            self.nodes[node].ids.push(id);
+        }
+        if let Some(entry) = self.entries[id].as_mut() {
+            entry.bounds = bounds;
+            entry.node = node;
+        }
+        true
+    }
+
+    // Visit the entries whose bounds pass `test`, skipping nodes whose loose bounds fail it.
+    // Root entries are always tested since they can lie outside the root cell.
+    fn query(&self, mut test: impl FnMut(&Aabb<T, D>) -> bool, mut visit: impl FnMut(usize, &V)) {
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            for &id in &node.ids {
+                if let Some(entry) = self.entry(id) {
+                    if test(&entry.bounds) {
+                        visit(id, &entry.value);
+                    }
+                }
+            }
+            if let Some(first) = node.children {
+                let children = first..first + (1 << D);
+                stack.extend(children.filter(|&c| test(&loose(&self.nodes[c].cell))));
+            }
+        }
+    }
+
+    /// Calls `visit` with the id and value of every entry whose bounds overlap `region`
+    pub fn query_region(&self, region: &Aabb<T, D>, visit: impl FnMut(usize, &V)) {
+        self.query(|bounds| region.intersects(bounds), visit);
+    }
+
+    /// Calls `visit` with every entry whose bounds `ray` crosses within `max_distance`, in
+    /// no particular order
+    pub fn query_ray(&self, ray: &Ray<T, D>, max_distance: T, visit: impl FnMut(usize, &V)) {
+        let inverse = Vector::from_array(array::from_fn(|i| T::one() / ray.direction[i]));
+        let test = |bounds: &Aabb<T, D>| ray_entry(ray, &inverse, bounds, max_distance).is_some();
+        self.query(test, visit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::{Octree, Quadtree};
+    use crate::geometry::{Aabb, Aabb2f64, Aabb3f64, Ray};
+    use crate::spatial::ray_entry;
+    use crate::spatial::test_util::{scattered_boxes, scattered_points, sorted};
+    use crate::vector::{Vector, Vector2f64, Vector3f64};
+
+    #[test]
+    fn test_quadtree_region_queries() {
+        let world = Aabb2f64::new(Vector2f64::new(-50.0, -50.0), Vector2f64::new(50.0, 50.0));
+        let mut tree = Quadtree::new(world, 6);
+        let boxes = scattered_boxes::<2>(300, -60.0, 60.0, 8.0, 5);
+        for (i, bounds) in boxes.iter().enumerate() {
+            assert_eq!(tree.insert(*bounds, i), i);
+        }
+        // Larger than the world
+        let huge = Aabb2f64::new(Vector2f64::new(-80.0, -1.0), Vector2f64::new(80.0, 1.0));
+        let huge_id = tree.insert(huge, 300);
+        assert_eq!(tree.len(), 301);
+        assert!(tree.nodes.len() > 1);
+        assert_eq!(tree.entries[huge_id].as_ref().unwrap().node, 0);
+
+        let check = |tree: &Quadtree<f64, usize>, region: Aabb2f64| {
+            let mut found = Vec::new();
+            tree.query_region(&region, |id, &value| {
+                assert_eq!(id, value);
+                found.push(id);
+            });
+            let expected = (0..=300).filter(|&id| {
+                tree.entry_bounds(id)
+                    .is_some_and(|bounds| bounds.intersects(&region))
+            });
+            assert_eq!(sorted(found), expected.collect::<Vec<_>>());
+        };
+        for region in scattered_boxes::<2>(20, -60.0, 60.0, 30.0, 9) {
+            check(&tree, region);
+        }
+
+        // Remove and move, then query again
+        for id in (0..300).step_by(3) {
+            assert_eq!(tree.remove(id), Some(id));
+        }
+        assert_eq!(tree.remove(0), None);
+        let offset = Vector2f64::new(7.5, -12.0);
+        for id in (1..300).step_by(3) {
+            let bounds = tree.entry_bounds(id).unwrap();
+            assert!(tree.move_to(id, Aabb::new(bounds.min + offset, bounds.max + offset)));
+        }
+        assert!(!tree.move_to(3, huge));
+        assert!(tree.move_to(huge_id, boxes[0]));
+        assert_eq!(tree.len(), 201);
+        for region in scattered_boxes::<2>(20, -60.0, 60.0, 30.0, 13) {
+            check(&tree, region);
+        }
+
+        // Freed ids are handed out again
+        let reused = tree.insert(boxes[1], 1000);
+        assert_eq!(reused % 3, 0);
+        assert_eq!(tree.get(reused), Some(&1000));
+    }
+
+    #[test]
+    fn test_octree_ray_queries() {
+        let world = Aabb3f64::new(
+            Vector3f64::new(-50.0, -50.0, -50.0),
+            Vector3f64::new(50.0, 50.0, 50.0),
+        );
+        let mut tree = Octree::new(world, 5);
+        let boxes = scattered_boxes::<3>(400, -60.0, 60.0, 6.0, 21);
+        for bounds in &boxes {
+            tree.insert(*bounds, ());
+        }
+
+        let origins = scattered_points::<3>(25, -60.0, 60.0, 17);
+        for (k, &origin) in origins.iter().enumerate() {
+            let direction = Vector3f64::new((k as f64).cos(), (k as f64 * 0.7).sin(), 0.3);
+            let ray = Ray::new(origin, direction);
+            let inverse = Vector::from_array(core::array::from_fn(|i| 1.0 / direction[i]));
+
+            let mut found = Vec::new();
+            tree.query_ray(&ray, 80.0, |id, _| found.push(id));
+            let expected =
+                (0..boxes.len()).filter(|&i| ray_entry(&ray, &inverse, &boxes[i], 80.0).is_some());
+            assert_eq!(sorted(found), expected.collect::<Vec<_>>());
+        }
+
+        *tree.get_mut(4).unwrap() = ();
+        assert!(tree.get(400).is_none());
+        assert!(!tree.is_empty());
+    }
+}
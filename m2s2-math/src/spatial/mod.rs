@@ -0,0 +1,42 @@
+mod bvh;
+mod kdtree;
+mod loose_tree;
+#[cfg(test)]
+mod test_util;
+
+pub use bvh::Bvh;
+pub use kdtree::{KdTree, KdTree2, KdTree3, Neighbor};
+pub use loose_tree::{LooseTree, Octree, Quadtree};
+
+use num_traits::Float;
+
+use crate::geometry::{Aabb, Ray};
+use crate::vector::Vector;
+
+// Parameter where the ray enters `bounds`, zero when it starts inside, `None` when it misses
+// before `max_distance`. `inverse` holds the reciprocals of the ray direction.
+pub(crate) fn ray_entry<T: Float, const D: usize>(
+    ray: &Ray<T, D>,
+    inverse: &Vector<T, D>,
+    bounds: &Aabb<T, D>,
+    max_distance: T,
+) -> Option<T> {
+    let (mut enter, mut exit) = (T::zero(), max_distance);
+    for i in 0..D {
+        let origin = ray.origin[i];
+        if ray.direction[i] == T::zero() {
+            if origin < bounds.min[i] || origin > bounds.max[i] {
+                return None;
+            }
+            continue;
+        }
+        let t0 = (bounds.min[i] - origin) * inverse[i];
+        let t1 = (bounds.max[i] - origin) * inverse[i];
+        enter = enter.max(t0.min(t1));
+        exit = exit.min(t0.max(t1));
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(enter)
+}
@@ -0,0 +1,63 @@
+use alloc::vec::Vec;
+use core::array;
+
+use crate::geometry::Aabb;
+use crate::vector::Vector;
+
+/// Deterministic values from a linear congruential generator
+struct Scatter {
+    state: u64,
+}
+
+impl Scatter {
+    fn new(seed: u64) -> Self {
+        Scatter { state: seed }
+    }
+
+    /// Next value in [0, 1)
+    fn next(&mut self) -> f64 {
+        self.state = self
+            .state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Point with every coordinate in [low, high)
+    fn point<const D: usize>(&mut self, low: f64, high: f64) -> Vector<f64, D> {
+        Vector::from_array(array::from_fn(|_| low + self.next() * (high - low)))
+    }
+}
+
+/// `count` points with coordinates in [low, high)
+pub(crate) fn scattered_points<const D: usize>(
+    count: usize,
+    low: f64,
+    high: f64,
+    seed: u64,
+) -> Vec<Vector<f64, D>> {
+    let mut scatter = Scatter::new(seed);
+    (0..count).map(|_| scatter.point(low, high)).collect()
+}
+
+/// `count` boxes with corners in [low, high) and sizes up to `max_size`
+pub(crate) fn scattered_boxes<const D: usize>(
+    count: usize,
+    low: f64,
+    high: f64,
+    max_size: f64,
+    seed: u64,
+) -> Vec<Aabb<f64, D>> {
+    let mut scatter = Scatter::new(seed);
+    (0..count)
+        .map(|_| {
+            let min = scatter.point(low, high);
+            Aabb::new(min, min + scatter.point(0.0, max_size))
+        })
+        .collect()
+}
+
+pub(crate) fn sorted(mut ids: Vec<usize>) -> Vec<usize> {
+    ids.sort_unstable();
+    ids
+}
@@ -0,0 +1,33 @@
+use crate::matrix::Matrix;
+use crate::vector::{Vector, Vector3f64};
+
+pub(crate) fn v3(x: f64, y: f64, z: f64) -> Vector3f64 {
+    Vector3f64::new(x, y, z)
+}
+
+/// Asserts every component of `a` is within `tolerance` of `b`
+pub(crate) fn assert_vec_near<const D: usize>(
+    a: Vector<f64, D>,
+    b: Vector<f64, D>,
+    tolerance: f64,
+) {
+    for (x, y) in a.as_slice().iter().zip(b.as_slice()) {
+        assert!((x - y).abs() < tolerance, "{:?} != {:?}", a, b);
+    }
+}
+
+/// `assert_vec_near` with a tolerance for results that should be exact up to rounding
+pub(crate) fn assert_vec_eq<const D: usize>(a: Vector<f64, D>, b: Vector<f64, D>) {
+    assert_vec_near(a, b, 1e-10);
+}
+
+/// Asserts every element of `a` is within `tolerance` of `b`
+pub(crate) fn assert_matrix_eq<const R: usize, const C: usize>(
+    a: &Matrix<f64, R, C>,
+    b: &Matrix<f64, R, C>,
+    tolerance: f64,
+) {
+    for (x, y) in a.as_slice().iter().zip(b.as_slice()) {
+        assert!((x - y).abs() < tolerance, "{:?} != {:?}", a, b);
+    }
+}
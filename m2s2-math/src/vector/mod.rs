@@ -19,11 +19,9 @@ pub type Vector4i64 = Vector4<i64>;
 pub type Vector4f32 = Vector4<f32>;
 pub type Vector4f64 = Vector4<f64>;
 
-use std::slice;
-use std::{
-    mem::MaybeUninit,
+use core::{
+    array,
     ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
-    ptr,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -52,6 +50,13 @@ impl<T> Vector4<T> {
     }
 }
 
+impl<T, const D: usize> Vector<T, D> {
+    #[inline]
+    pub const fn from_array(data: [T; D]) -> Self {
+        Vector { data }
+    }
+}
+
 impl<T: Copy, const D: usize> Vector<T, D> {
     pub fn from_slice(elements: &[T]) -> Self {
         assert_eq!(
@@ -60,12 +65,9 @@ impl<T: Copy, const D: usize> Vector<T, D> {
             "Incorrect number of elements for dimension"
         );
 
-        let mut data_arr: MaybeUninit<[T; D]> = MaybeUninit::uninit();
-        let ptr = data_arr.as_mut_ptr() as *mut T;
-        let slice = unsafe { slice::from_raw_parts_mut(ptr, D) };
-        slice.copy_from_slice(elements);
-        let data = unsafe { data_arr.assume_init() };
-        Vector { data }
+        Vector {
+            data: array::from_fn(|i| elements[i]),
+        }
     }
 
     pub fn get(&self, index: usize) -> Option<T> {
@@ -105,14 +107,9 @@ impl<T: Copy, const D: usize> Vector<T, D> {
     where
         F: Fn(T, T) -> T,
     {
-        let mut res: MaybeUninit<[T; D]> = MaybeUninit::uninit();
-        let res_ptr = res.as_mut_ptr() as *mut T;
-        for i in 0..D {
-            unsafe { ptr::write(res_ptr.add(i), computed_val(self.data[i], rhs.data[i])) };
+        Vector {
+            data: array::from_fn(|i| computed_val(self.data[i], rhs.data[i])),
         }
-
-        let data = unsafe { res.assume_init() };
-        Vector { data }
     }
 }
 
@@ -174,30 +171,18 @@ impl<T: SubAssign + Copy, const D: usize> SubAssign<&Self> for Vector<T, D> {
 impl<T: Mul<Output = T> + Copy, const D: usize> Mul<T> for Vector<T, D> {
     type Output = Self;
     fn mul(self, scalar: T) -> Self {
-        let mut res: MaybeUninit<[T; D]> = MaybeUninit::uninit();
-        let res_ptr = res.as_mut_ptr() as *mut T;
-        for i in 0..D {
-            let computed_val = self.data[i] * scalar;
-            unsafe { ptr::write(res_ptr.add(i), computed_val) };
+        Vector {
+            data: array::from_fn(|i| self.data[i] * scalar),
         }
-
-        let data = unsafe { res.assume_init() };
-        Vector { data }
     }
 }
 
 impl<T: Neg<Output = T> + Copy, const D: usize> Neg for Vector<T, D> {
     type Output = Self;
     fn neg(self) -> Self {
-        let mut res: MaybeUninit<[T; D]> = MaybeUninit::uninit();
-        let res_ptr = res.as_mut_ptr() as *mut T;
-        for i in 0..D {
-            let computed_val = -self.data[i];
-            unsafe { ptr::write(res_ptr.add(i), computed_val) };
+        Vector {
+            data: array::from_fn(|i| -self.data[i]),
         }
-
-        let data = unsafe { res.assume_init() };
-        Vector { data }
     }
 }
 
@@ -1,5 +1,5 @@
-use crate::vector::{Vector2, Vector3, Vector4};
-use num_traits::{Float, Zero};
+use crate::vector::{Vector2, Vector3};
+use num_traits::Float;
 
 /// Trait for 3D vector operations
 pub trait Vector3Ops<T>
@@ -139,7 +139,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::vector::{Vector2f32, Vector2f64, Vector3f32, Vector3f64};
+    use crate::vector::{Vector2f32, Vector3f32, Vector3f64};
 
     #[test]
     fn test_vector3_f32_operations() {